@@ -0,0 +1,443 @@
+//! Blocking versions of the HTTP API calls are implemented under this module.
+//!
+//! In this section, there is a blocking [`Client`] which wraps the async
+//! [`Client`][crate::Client] and blocks the current thread until each call
+//! completes. There is no need to set up an async runtime to use it.
+
+use std::{collections::HashMap, ops::RangeBounds, sync::Arc, time::Duration};
+
+use futures::executor::block_on;
+use rust_decimal::Decimal;
+
+use crate::{
+    error::SendRequest,
+    http::{
+        private::{
+            user_transactions::TransactionType, AssetBalance, CancelResult,
+            CryptoTransaction, FiatTransaction, NewOrder, OpenOrders, Order,
+            OrderSpec, ReplaceOutcome, SubmitOutcome, TradeTransaction,
+        },
+        public::{
+            order_book::SlippageEstimate, ticker::Currency, ExchangeInfo, Ohlc,
+            OhlcResolution, OrderBook, Ticker, Trade,
+        },
+        Health, OrderType, RateLimitInfo,
+    },
+    ApiKeys,
+};
+
+/** Used to send HTTP requests and block until the responses are received.
+
+It has the same methods as the async [`Client`][crate::Client] but they
+return the results directly instead of futures.
+# Examples
+## Get ticker
+```no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use btcturk::blocking::Client;
+
+let client = Client::new(None, None)?;
+
+let btc_price = client.ticker("BTCUSDT")?.last;
+
+# Ok(())
+# }
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: crate::Client,
+}
+
+impl From<crate::Client> for Client {
+    fn from(inner: crate::Client) -> Self {
+        Self { inner }
+    }
+}
+
+impl Client {
+    /// Construct a client. See [`Client::new`][crate::Client::new].
+    /// # Errors
+    /// A [`surf`] error will occur if there is an error building an HTTP
+    /// client.
+    /// # Panics
+    /// If any of the hardcoded endpoint URLs can't be parsed, this function
+    /// will panic.
+    pub fn new(keys: Option<ApiKeys>, id: Option<&str>) -> surf::Result<Self> {
+        crate::Client::new(keys, id).map(Self::from)
+    }
+
+    /// Get a reference to the wrapped async client.
+    #[must_use]
+    pub const fn inner(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Set the client's API keys. You can remove the current
+    /// keys by passing `None`.
+    pub fn set_keys(&mut self, keys: Option<ApiKeys>) {
+        self.inner.set_keys(keys);
+    }
+
+    /// Get the client's API keys, if any. See
+    /// [`Client::keys`][crate::Client::keys].
+    #[must_use]
+    pub const fn keys(&self) -> Option<&ApiKeys> {
+        self.inner.keys()
+    }
+
+    /// Whether the client has API keys. See
+    /// [`Client::is_authenticated`][crate::Client::is_authenticated].
+    #[must_use]
+    pub const fn is_authenticated(&self) -> bool {
+        self.inner.is_authenticated()
+    }
+
+    /// Set the client's identifier. You can remove the current
+    /// identifier by passing `None`.
+    pub fn set_id(&mut self, id: Option<&str>) {
+        self.inner.set_id(id);
+    }
+
+    /// Get the client's id.
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.inner.id()
+    }
+
+    /// Get the rate limit information of the last response which had any.
+    #[must_use]
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        self.inner.last_rate_limit()
+    }
+
+    /// Get how long the last request took. See
+    /// [`Client::last_latency`][crate::Client::last_latency].
+    #[must_use]
+    pub fn last_latency(&self) -> Option<Duration> {
+        self.inner.last_latency()
+    }
+
+    /// Set how many milliseconds the local clock is ahead of the server
+    /// clock. See [`Client::set_clock_offset`][crate::Client::set_clock_offset].
+    pub fn set_clock_offset(&self, clock_offset: i64) {
+        self.inner.set_clock_offset(clock_offset);
+    }
+}
+
+/// Define blocking methods which forward their arguments to the async methods
+/// with the same names. Methods which return their errors inside the output,
+/// e.g. per order, are listed after `infallible:` so that they aren't
+/// documented with an errors section.
+macro_rules! blocking {
+    (infallible: $(
+        fn $name:ident(&self $(, $arg:ident: $type:ty)*) -> $output:ty;
+    )*) => {
+        impl Client {
+            $(blocking!(@method [] $name($($arg: $type),*) -> $output);)*
+        }
+    };
+    ($(fn $name:ident(&self $(, $arg:ident: $type:ty)*) -> $output:ty;)*) => {
+        impl Client {
+            $(blocking!(@method [
+                "# Errors",
+                "[`SendRequest`] if there is an error sending the request or",
+                "there is an error or a malformation in the received response."
+            ] $name($($arg: $type),*) -> $output);)*
+        }
+    };
+    (@method [$($doc:literal),*]
+        $name:ident($($arg:ident: $type:ty),*) -> $output:ty
+    ) => {
+        #[doc = concat!(
+            "Blocking version of [`Client::", stringify!($name),
+            "`][crate::Client::", stringify!($name), "]."
+        )]
+        $(#[doc = $doc])*
+        pub fn $name(&self $(, $arg: $type)*) -> $output {
+            block_on(self.inner.$name($($arg),*))
+        }
+    };
+}
+
+blocking! {
+    fn ticker(
+        &self,
+        pair_symbol: impl Into<String> + Send
+    ) -> Result<Ticker, SendRequest>;
+    fn tickers(&self) -> Result<Vec<Ticker>, SendRequest>;
+    fn tickers_map(&self) -> Result<HashMap<String, Ticker>, SendRequest>;
+    fn currency(&self, symbol: Currency) -> Result<Vec<Ticker>, SendRequest>;
+    fn currency_by_symbol(
+        &self,
+        symbol: impl Into<String> + Send
+    ) -> Result<Vec<Ticker>, SendRequest>;
+    fn trades(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        last: Option<u8>
+    ) -> Result<Vec<Trade>, SendRequest>;
+    fn ohlc(
+        &self,
+        pair: impl Into<String> + Send,
+        range: impl RangeBounds<u64> + Send
+    ) -> Result<Vec<Ohlc>, SendRequest>;
+    fn ohlc_latest(
+        &self,
+        pair: impl Into<String> + Send,
+        count: usize
+    ) -> Result<Vec<Ohlc>, SendRequest>;
+    fn ohlc_with_resolution(
+        &self,
+        pair: impl Into<String> + Send,
+        range: impl RangeBounds<u64> + Send,
+        resolution: OhlcResolution
+    ) -> Result<Vec<Ohlc>, SendRequest>;
+    fn order_book(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        limit: Option<u16>
+    ) -> Result<OrderBook, SendRequest>;
+    fn estimate_market_slippage(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        side: OrderType,
+        quantity: Decimal
+    ) -> Result<SlippageEstimate, SendRequest>;
+    fn exchange_info(&self) -> Result<ExchangeInfo, SendRequest>;
+    fn exchange_info_cached(&self) -> Result<Arc<ExchangeInfo>, SendRequest>;
+    fn tradable_pairs(&self) -> Result<Vec<String>, SendRequest>;
+    fn server_time(&self) -> Result<u64, SendRequest>;
+    fn clock_offset(&self) -> Result<i64, SendRequest>;
+    fn sync_clock(&self) -> Result<i64, SendRequest>;
+    fn health(&self) -> Result<Health, SendRequest>;
+    fn account_balance(&self) -> Result<Vec<AssetBalance>, SendRequest>;
+    fn balance_of(
+        &self,
+        asset: &str
+    ) -> Result<Option<AssetBalance>, SendRequest>;
+    fn account_balance_for(
+        &self,
+        assets: &[&str]
+    ) -> Result<Vec<AssetBalance>, SendRequest>;
+    fn nonzero_balances(&self) -> Result<Vec<AssetBalance>, SendRequest>;
+    fn balances_map(
+        &self
+    ) -> Result<HashMap<String, AssetBalance>, SendRequest>;
+    fn trade_transactions(
+        &self,
+        order_id: Option<i64>,
+        r#type: Option<OrderType>,
+        symbols: Vec<impl Into<String> + Send>,
+        date_range: impl RangeBounds<u64> + Send
+    ) -> Result<Vec<TradeTransaction>, SendRequest>;
+    fn crypto_transactions(
+        &self,
+        r#type: Option<TransactionType>,
+        symbols: Vec<impl Into<String> + Send>,
+        date_range: impl RangeBounds<u64> + Send
+    ) -> Result<Vec<CryptoTransaction>, SendRequest>;
+    fn fiat_transactions(
+        &self,
+        r#type: Option<TransactionType>,
+        symbols: Vec<impl Into<String> + Send>,
+        date_range: impl RangeBounds<u64> + Send
+    ) -> Result<Vec<FiatTransaction>, SendRequest>;
+    fn open_orders(
+        &self,
+        pair_symbol: impl Into<String> + Send
+    ) -> Result<OpenOrders, SendRequest>;
+    fn all_orders(
+        &self,
+        order_id: Option<i64>,
+        pair_symbol: impl Into<String> + Send,
+        time_range: impl RangeBounds<u64> + Send,
+        page: Option<u64>,
+        limit: Option<u16>
+    ) -> Result<Vec<Order>, SendRequest>;
+    fn order(
+        &self,
+        id: i64,
+        pair_symbol: impl Into<String> + Send
+    ) -> Result<Option<Order>, SendRequest>;
+    fn market_buy(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn market_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn market_buy_quote(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quote_amount: Decimal
+    ) -> Result<(NewOrder, Decimal), SendRequest>;
+    fn market_sell(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn market_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn limit_buy(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn limit_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn limit_sell(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn limit_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_limit_buy(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_limit_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_limit_sell(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_limit_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_market_buy(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_market_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_market_sell(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal
+    ) -> Result<NewOrder, SendRequest>;
+    fn stop_market_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send
+    ) -> Result<NewOrder, SendRequest>;
+    fn submit(&self, spec: OrderSpec) -> Result<NewOrder, SendRequest>;
+    fn submit_validated(
+        &self,
+        spec: OrderSpec
+    ) -> Result<NewOrder, SendRequest>;
+    fn cancel_order(&self, id: i64) -> Result<CancelResult, SendRequest>;
+}
+
+blocking! {
+    infallible:
+    fn ohlc_many(
+        &self,
+        pairs: &[&str],
+        range: impl RangeBounds<u64> + Send,
+        concurrency: usize
+    ) -> Vec<(String, Result<Vec<Ohlc>, SendRequest>)>;
+    fn submit_idempotent(
+        &self,
+        spec: OrderSpec,
+        client_id: impl Into<String> + Send
+    ) -> SubmitOutcome;
+    fn submit_orders(
+        &self,
+        orders: Vec<OrderSpec>,
+        concurrency: usize
+    ) -> Vec<Result<NewOrder, SendRequest>>;
+    fn cancel_orders(
+        &self,
+        ids: &[i64],
+        concurrency: usize
+    ) -> Vec<(i64, Result<CancelResult, SendRequest>)>;
+    fn replace_order(
+        &self,
+        old_id: i64,
+        new_spec: OrderSpec
+    ) -> ReplaceOutcome;
+}
+
+#[cfg(feature = "csv")]
+blocking! {
+    fn export_trade_transactions_csv(
+        &self,
+        writer: impl std::io::Write + Send,
+        date_range: std::ops::Range<u64>
+    ) -> Result<usize, SendRequest>;
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_str_eq;
+
+    use super::Client;
+    use crate::http::mock::MockHttpClient;
+
+    #[test]
+    fn send_blocking() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let backend = MockHttpClient::new();
+        backend.push_data(&format!(
+            "[{}]",
+            include_str!("../http/public/ticker/sample.json")
+        ));
+        let client = Client::from(crate::Client::with_http_client(
+            backend.client(),
+            None,
+            None,
+        ));
+        let ticker = client.ticker("BTCUSDT").unwrap();
+        assert_str_eq!(ticker.pair, "BTCUSDT");
+    }
+}
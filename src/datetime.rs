@@ -0,0 +1,46 @@
+//! Conversions from the raw timestamps to [`DateTime`].
+//!
+//! Values which don't fit in a [`DateTime`] saturate to
+//! [`DateTime::MAX_UTC`] (or [`DateTime::MIN_UTC`]) instead of panicking.
+
+use chrono::{DateTime, Utc};
+
+/// Convert milliseconds since the Unix epoch.
+pub fn from_millis(millis: u64) -> DateTime<Utc> {
+    i64::try_from(millis)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// Convert seconds since the Unix epoch.
+pub fn from_secs(secs: u64) -> DateTime<Utc> {
+    i64::try_from(secs)
+        .ok()
+        .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use pretty_assertions::assert_eq;
+
+    use super::{from_millis, from_secs};
+
+    #[test]
+    fn convert_timestamps() {
+        let expected = DateTime::from_timestamp_millis(1_643_883_463_379);
+        assert_eq!(Some(from_millis(1_643_883_463_379)), expected);
+        assert_eq!(
+            Some(from_secs(1_639_526_400)),
+            DateTime::from_timestamp(1_639_526_400, 0)
+        );
+    }
+
+    #[test]
+    fn saturate_out_of_range() {
+        assert_eq!(from_millis(u64::MAX), DateTime::<Utc>::MAX_UTC);
+        assert_eq!(from_secs(u64::MAX), DateTime::<Utc>::MAX_UTC);
+    }
+}
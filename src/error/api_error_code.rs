@@ -0,0 +1,70 @@
+use std::fmt::Display;
+
+/// Code of an error returned by the API.
+///
+/// Only the codes whose meaning is known are mapped to a variant. Other codes
+/// are kept in [`Unknown`][Self::Unknown] so that they can still be matched.
+/// More codes may be mapped later, so match [`code`][Self::code] rather than
+/// `Unknown` for a code without a variant.
+///
+/// The orders rejected for a too low exchange value or a price outside the
+/// band can be caught before sending them with
+/// [`submit_validated`][crate::Client::submit_validated] and
+/// [`estimate_market_slippage`][crate::Client::estimate_market_slippage].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ApiErrorCode {
+    /// A mandatory parameter is missing. The message tells which one, e.g.
+    /// `currencySymbol parameter must be set`.
+    MissingParameter,
+    /// Any other code.
+    Unknown(i64),
+}
+
+impl ApiErrorCode {
+    /// Get the numeric code.
+    #[must_use]
+    pub const fn code(self) -> i64 {
+        match self {
+            Self::MissingParameter => 1037,
+            Self::Unknown(code) => code,
+        }
+    }
+}
+
+impl From<i64> for ApiErrorCode {
+    fn from(code: i64) -> Self {
+        match code {
+            1037 => Self::MissingParameter,
+            code => Self::Unknown(code),
+        }
+    }
+}
+
+impl From<ApiErrorCode> for i64 {
+    fn from(code: ApiErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::ApiErrorCode;
+
+    #[test]
+    fn code_round_trip() {
+        for code in [1037, 0, 9999] {
+            assert_eq!(ApiErrorCode::from(code).code(), code);
+        }
+        assert_eq!(ApiErrorCode::from(1037), ApiErrorCode::MissingParameter);
+        assert_eq!(ApiErrorCode::from(9999), ApiErrorCode::Unknown(9999));
+    }
+}
@@ -0,0 +1,38 @@
+use std::{env::VarError, io};
+
+use thiserror::Error;
+
+use super::PrivateKey;
+
+/// Occurs when the API keys can't be loaded from the environment or a file.
+#[derive(Error, Debug)]
+pub enum LoadKeys {
+    /// Environment variable is not set or is not valid unicode.
+    #[error("failed to read environment variable `{name}`")]
+    VarError {
+        /// Name of the variable.
+        name: &'static str,
+        /// Source of the error.
+        source: VarError,
+    },
+    /// Keys file couldn't be read.
+    #[error("failed to read the keys file")]
+    IoError {
+        /// Source of the error.
+        #[from]
+        source: io::Error,
+    },
+    /// Keys file doesn't have a line for one of the keys.
+    #[error("keys file is missing the line of the {key} key")]
+    MissingLine {
+        /// `public` or `private`.
+        key: &'static str,
+    },
+    /// Loaded keys are invalid.
+    #[error(transparent)]
+    PrivateKeyError {
+        /// Source of the error.
+        #[from]
+        source: PrivateKey,
+    },
+}
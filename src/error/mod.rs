@@ -14,3 +14,9 @@ pub use parse::Parse;
 
 mod private_key;
 pub use private_key::PrivateKey;
+
+mod load_keys;
+pub use load_keys::LoadKeys;
+
+mod api_error_code;
+pub use api_error_code::ApiErrorCode;
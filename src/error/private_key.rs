@@ -2,7 +2,7 @@ use base64::DecodeError;
 use crypto_common::InvalidLength;
 use thiserror::Error;
 
-/// Occurs when there is an error with the private API key.
+/// Occurs when there is an error with the private or the public API key.
 #[derive(Error, Debug, Clone)]
 pub enum PrivateKey {
     /// Invalid length error occurred.
@@ -19,4 +19,10 @@ pub enum PrivateKey {
         #[from]
         source: DecodeError,
     },
+    /// Public key is not in the hyphenated UUID form.
+    #[error("invalid public key: `{public_key}`")]
+    InvalidPublicKey {
+        /// Rejected public key.
+        public_key: String,
+    },
 }
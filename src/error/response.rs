@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use super::ApiErrorCode;
+
 /// Occurs when there is an error in the received response such as
 /// server-side error value or empty data field.
 ///
@@ -13,11 +15,52 @@ pub enum Response {
         message: Option<String>,
         /// `code` field of the response.
         code: i64,
+        /// JSON string of the response. Useful to debug unexpected
+        /// responses.
+        response_string: String,
     },
-    /// Null `data` field
+    /// Null or missing `data` field where a single value is expected. A null
+    /// list is returned as an empty list instead.
     #[error("null `data` field")]
     NullData,
-    /// Empty `data` field
+    /// Empty list in the `data` field where at least one item is expected.
+    #[deprecated(
+        note = "it is no longer returned. An empty list is returned as \
+        `Ok(vec![])`."
+    )]
     #[error("empty `data` field")]
     EmptyData,
+    /// Kline endpoint returned the `error` status.
+    #[error("kline error. message: {message:?}")]
+    KlineError {
+        /// `errmsg` field of the response, if any.
+        message: Option<String>,
+    },
+    /// Columns of the klines have different lengths so the candles can't be
+    /// put together.
+    #[error("kline columns have different lengths")]
+    KlineColumnMismatch,
+    /// Requested pair is not listed, e.g. the symbol has a typo.
+    #[error("pair `{pair}` is not found")]
+    PairNotFound {
+        /// Requested pair symbol.
+        pair: String,
+    },
+}
+
+impl Response {
+    /// Get the typed code of an [`Unsuccessful`][Self::Unsuccessful]
+    /// response.
+    #[must_use]
+    #[allow(deprecated)]
+    pub fn api_error_code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Self::Unsuccessful { code, .. } => Some((*code).into()),
+            Self::NullData
+            | Self::EmptyData
+            | Self::KlineError { .. }
+            | Self::KlineColumnMismatch
+            | Self::PairNotFound { .. } => None,
+        }
+    }
 }
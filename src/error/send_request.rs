@@ -1,9 +1,9 @@
-use std::time::SystemTimeError;
+use std::time::{Duration, SystemTimeError};
 
 use surf::StatusCode;
 use thiserror::Error;
 
-use super::{Parameter, Response};
+use super::{ApiErrorCode, Parameter, Response};
 
 /// Occurs when there is an error sending a request.
 #[derive(Error, Debug)]
@@ -24,6 +24,34 @@ pub enum SendRequest {
         /// Deserialized response's message, if any.
         message: Option<String>,
     },
+    /// Request didn't complete in time. See
+    /// [`ClientBuilder::timeout`][crate::http::ClientBuilder::timeout].
+    #[error("request timed out after {timeout:?}")]
+    Timeout {
+        /// Time the request was allowed to take.
+        timeout: Duration,
+    },
+    /// Signed request couldn't be sent within the receive window, e.g. it
+    /// waited for the rate limit or a retry for too long. It wasn't sent.
+    /// See
+    /// [`ClientBuilder::recv_window`][crate::http::ClientBuilder::recv_window].
+    #[error("signed request was not sent within {recv_window:?}")]
+    Expired {
+        /// Time the request was allowed to wait before being sent.
+        recv_window: Duration,
+    },
+    /// Local clock is off from the server clock by more than the receive
+    /// window. See [`Client::sync_clock`][crate::Client::sync_clock].
+    #[error(
+        "local clock is off by {clock_offset} ms which is more than the \
+        receive window of {recv_window:?}"
+    )]
+    ClockSkew {
+        /// Milliseconds the local clock is ahead of the server clock.
+        clock_offset: i64,
+        /// Receive window of the client.
+        recv_window: Duration,
+    },
     /// System time error occurred.
     #[error(transparent)]
     SystemTimeError {
@@ -52,6 +80,16 @@ pub enum SendRequest {
         #[from]
         source: serde_json::Error,
     },
+    /// Received response couldn't be deserialized.
+    #[error("failed to deserialize the response at `{path}`: {source}")]
+    DeserializeError {
+        /// Path of the value which failed to deserialize, e.g.
+        /// `data[3].status`.
+        path: String,
+        /// Source of the error.
+        #[source]
+        source: serde_json::Error,
+    },
     /// Response error occurred.
     #[error(transparent)]
     ResponseError {
@@ -66,6 +104,52 @@ pub enum SendRequest {
         #[from]
         source: Parameter,
     },
+    /// CSV error occurred.
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    CsvError {
+        /// Source of the error.
+        #[from]
+        source: csv::Error,
+    },
+    /// Websocket error occurred.
+    #[error(transparent)]
+    WebsocketError {
+        /// Source of the error.
+        #[from]
+        source: async_tungstenite::tungstenite::Error,
+    },
+    /// Websocket connection was closed by the server or dropped.
+    #[error("websocket connection closed")]
+    ConnectionClosed,
+    /// Websocket feed rejected the login of an authenticated channel.
+    #[error("websocket login failed with message `{message:?}`")]
+    LoginFailed {
+        /// Message sent by the feed, if any.
+        message: Option<String>,
+    },
+    /// Websocket update skipped one or more sequence numbers. Resubscribe to
+    /// the channel to receive a fresh snapshot.
+    #[error("expected sequence number `{expected}`, got `{received}`")]
+    SequenceGap {
+        /// Sequence number following the last received one.
+        expected: u64,
+        /// Sequence number of the received update.
+        received: u64,
+    },
+}
+
+impl SendRequest {
+    /// Get the typed code of the error returned by the API, if any. Use it to
+    /// branch on the reason of a failure, e.g. when submitting an order.
+    #[must_use]
+    pub fn api_error_code(&self) -> Option<ApiErrorCode> {
+        match self {
+            Self::BadStatusCode { code, .. } => code.map(Into::into),
+            Self::ResponseError { source } => source.api_error_code(),
+            _ => None,
+        }
+    }
 }
 
 impl From<surf::Error> for SendRequest {
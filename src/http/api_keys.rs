@@ -1,11 +1,15 @@
 use std::{
-    fmt::Display,
+    env,
+    fmt::{self, Debug, Display},
+    fs,
     hash::Hash,
+    path::Path,
     time::{SystemTime, SystemTimeError, UNIX_EPOCH},
 };
 
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
+use zeroize::Zeroizing;
 
 use crate::error;
 
@@ -19,45 +23,89 @@ use crate::error;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+///
+/// The private key, in its text and its decoded form, is wiped from the memory
+/// when the keys are dropped and it is redacted in the [`Display`] and
+/// [`Debug`] outputs. The signing state derived from it is built for each
+/// signature and dropped right after it without being wiped.
+#[derive(Clone)]
 pub struct ApiKeys {
     public_key: String,
-    private_key: String,
-    mac: Hmac<Sha256>,
+    private_key: Zeroizing<String>,
+    /// Decoded private key which the requests are signed with.
+    secret: Zeroizing<Vec<u8>>,
 }
 
 impl ApiKeys {
     /// Creates new API keys object by the given public/private keys.
     /// # Errors
     /// [`PrivateKey`][error::PrivateKey] error occurs if the private key length
-    /// is invalid.
+    /// is invalid or the public key is not a UUID, e.g.
+    /// `63762e79-cb5c-4c0b-b714-5f0ce94bf100`.
     pub fn new(
         public_key: impl Into<String>,
         private_key: impl Into<String>,
     ) -> Result<Self, error::PrivateKey> {
-        let private_key = private_key.into();
+        let public_key = public_key.into();
+        if !is_uuid(&public_key) {
+            return Err(error::PrivateKey::InvalidPublicKey { public_key });
+        }
+        let private_key = Zeroizing::new(private_key.into());
+        let secret = Zeroizing::new(base64::decode(private_key.as_str())?);
+        Hmac::<Sha256>::new_from_slice(&secret)?;
         Ok(Self {
-            public_key: public_key.into(),
-            private_key: private_key.clone(),
-            mac: Hmac::<Sha256>::new_from_slice(&base64::decode(
-                &private_key,
-            )?)?,
+            public_key,
+            private_key,
+            secret,
         })
     }
 
+    /// Load API keys from the `BTCTURK_PUBLIC_KEY` and `BTCTURK_PRIVATE_KEY`
+    /// environment variables.
+    /// # Errors
+    /// [`LoadKeys`][error::LoadKeys] error occurs if a variable is not set or
+    /// the keys are invalid.
+    pub fn from_env() -> Result<Self, error::LoadKeys> {
+        let var = |name| {
+            env::var(name)
+                .map_err(|source| error::LoadKeys::VarError { name, source })
+        };
+        let public_key = var("BTCTURK_PUBLIC_KEY")?;
+        let private_key = Zeroizing::new(var("BTCTURK_PRIVATE_KEY")?);
+        Ok(Self::new(public_key, private_key.as_str())?)
+    }
+
+    /// Load API keys from a file which consists of two lines of text: Public
+    /// key and private key. Whitespace around the keys is ignored.
+    /// # Errors
+    /// [`LoadKeys`][error::LoadKeys] error occurs if the file can't be read,
+    /// a line is missing or the keys are invalid.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, error::LoadKeys> {
+        let text = Zeroizing::new(fs::read_to_string(path)?);
+        Self::from_lines(&text)
+    }
+
+    fn from_lines(text: &str) -> Result<Self, error::LoadKeys> {
+        let mut lines = text.lines().map(str::trim);
+        let mut line = |key| {
+            lines
+                .next()
+                .filter(|line| !line.is_empty())
+                .ok_or(error::LoadKeys::MissingLine { key })
+        };
+        let public_key = line("public")?;
+        let private_key = line("private")?;
+        Ok(Self::new(public_key, private_key)?)
+    }
+
     /// Load API keys from a file path passed by `KEYS_PATH` environment var.
-    /// The variable stores the path to the keys file which consist of two
-    /// lines of text: Public key and secret key.
+    /// See [`from_file`][Self::from_file].
     ///
     /// Example: `KEYS_PATH={path} cargo test -- --ignored`
     #[cfg(test)]
     pub fn load_from_env_var() -> Self {
         if let Ok(path) = std::env::var("KEYS_PATH") {
-            let key_str = std::fs::read_to_string(path).unwrap();
-            let mut lines = key_str.lines();
-            let public_key = lines.next().unwrap().to_owned();
-            let private_key = lines.next().unwrap().to_owned();
-            return Self::new(public_key, private_key).unwrap();
+            return Self::from_file(path).unwrap();
         }
         panic!(
             "KEYS_PATH environment var is missing. The key file must consist
@@ -78,33 +126,51 @@ impl ApiKeys {
         self.private_key.as_ref()
     }
 
-    /// Sign the query part of a request's URL.
-    /// # Errors
-    /// [`SystemTimeError`] occurs if there is an error retrieving the current
-    /// timestamp (_nonce_) of the system.
-    /// # Returns
-    /// Sign and nonce(timestamp) values in a tuple, respectively.
-    pub(crate) fn generate_sign_nonce(
-        &self,
-    ) -> Result<(String, String), SystemTimeError> {
-        let mut mac = self.mac.clone();
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)?
-            .as_millis()
-            .to_string();
-        mac.update((self.public_key.clone() + &timestamp).as_bytes());
-        let signature: String = base64::encode(mac.finalize().into_bytes());
-        Ok((signature, timestamp))
+    /// Sign the nonce, i.e. the timestamp of a request in milliseconds.
+    pub(crate) fn sign(&self, nonce: u64) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret)
+            .expect("the key length is validated by the constructor");
+        mac.update((self.public_key.clone() + &nonce.to_string()).as_bytes());
+        base64::encode(mac.finalize().into_bytes())
     }
 }
 
+/// Get the nonce of a request which is the local time in milliseconds minus
+/// the `clock_offset`, i.e. the number of milliseconds the local clock is
+/// ahead of the server clock. Sign it with [`ApiKeys::sign`].
+/// # Errors
+/// [`SystemTimeError`] if there is an error retrieving the local time.
+pub fn nonce(clock_offset: i64) -> Result<u64, SystemTimeError> {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    Ok(u64::try_from(millis)
+        .unwrap_or(u64::MAX)
+        .saturating_add_signed(clock_offset.saturating_neg()))
+}
+
+/// Check the shape of the public keys which is the hyphenated hexadecimal
+/// form of a UUID.
+fn is_uuid(key: &str) -> bool {
+    key.len() == 36
+        && key.char_indices().all(|(index, char)| match index {
+            8 | 13 | 18 | 23 => char == '-',
+            _ => char.is_ascii_hexdigit(),
+        })
+}
+
 impl Display for ApiKeys {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Public Key: {}, Private Key: {}",
-            self.public_key, self.private_key
-        )
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Public Key: {}, Private Key: ***", self.public_key)
+    }
+}
+
+/// Only the first group of the public key is printed.
+impl Debug for ApiKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = self.public_key.get(..8).unwrap_or_default();
+        f.debug_struct("ApiKeys")
+            .field("public_key", &format_args!("{prefix}..."))
+            .field("private_key", &format_args!("***"))
+            .finish_non_exhaustive()
     }
 }
 
@@ -130,7 +196,10 @@ mod tests {
     use log::info;
     use sha2::Sha256;
 
-    use crate::http::ApiKeys;
+    use crate::{
+        error::{LoadKeys, PrivateKey},
+        http::ApiKeys,
+    };
 
     #[test]
     fn verify_sign() {
@@ -142,7 +211,8 @@ mod tests {
 
         let keys =
             ApiKeys::new(public_key.clone(), private_key.clone()).unwrap();
-        let (sign, nonce) = keys.generate_sign_nonce().unwrap();
+        let nonce = super::nonce(0).unwrap();
+        let sign = keys.sign(nonce);
 
         info!("sign: {}, nonce: {}", sign, nonce);
 
@@ -154,7 +224,72 @@ mod tests {
             &base64::decode(private_key).unwrap(),
         )
         .unwrap();
-        mac.update((public_key.to_owned() + nonce.as_str()).as_bytes());
+        mac.update((public_key.to_owned() + &nonce.to_string()).as_bytes());
         mac.verify_slice(sign_bytes.as_slice()).unwrap();
     }
+
+    #[test]
+    fn reject_invalid_public_key() {
+        let private_key = "L2tW3CeHzXH16im1pIhofRw0GdlqCdb8";
+        for public_key in [
+            "",
+            "PUBLIC_KEY",
+            "63762e79cb5c4c0bb7145f0ce94bf100",
+            "63762e79-cb5c-4c0b-b714-5f0ce94bf10g",
+            "63762e79-cb5c-4c0b-b714-5f0ce94bf100 ",
+        ] {
+            let Err(PrivateKey::InvalidPublicKey { public_key: key }) =
+                ApiKeys::new(public_key, private_key)
+            else {
+                panic!("expected an invalid public key: `{public_key}`");
+            };
+            assert_eq!(key, public_key);
+        }
+        ApiKeys::new("63762E79-CB5C-4C0B-B714-5F0CE94BF100", private_key)
+            .unwrap();
+    }
+
+    #[test]
+    fn load_keys_from_file() {
+        let path = std::env::temp_dir().join("btcturk_load_keys_from_file");
+        std::fs::write(
+            &path,
+            "63762e79-cb5c-4c0b-b714-5f0ce94bf100\r\n\
+            L2tW3CeHzXH16im1pIhofRw0GdlqCdb8\r\n",
+        )
+        .unwrap();
+        let keys = ApiKeys::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            keys.unwrap().public_key(),
+            "63762e79-cb5c-4c0b-b714-5f0ce94bf100"
+        );
+
+        let Err(LoadKeys::MissingLine { key }) =
+            ApiKeys::from_lines("63762e79-cb5c-4c0b-b714-5f0ce94bf100\n\n")
+        else {
+            panic!("expected a missing line");
+        };
+        assert_eq!(key, "private");
+        let Err(LoadKeys::IoError { .. }) = ApiKeys::from_file(&path) else {
+            panic!("expected an IO error");
+        };
+    }
+
+    #[test]
+    fn redact_private_key() {
+        let private_key = "L2tW3CeHzXH16im1pIhofRw0GdlqCdb8";
+        let keys =
+            ApiKeys::new("63762e79-cb5c-4c0b-b714-5f0ce94bf100", private_key)
+                .unwrap();
+        assert_eq!(keys.private_key(), private_key);
+        for output in [keys.to_string(), format!("{keys:?}")] {
+            assert!(!output.contains(private_key), "{output}");
+            assert!(output.contains("***"), "{output}");
+        }
+        assert_eq!(
+            format!("{keys:?}"),
+            "ApiKeys { public_key: 63762e79..., private_key: ***, .. }"
+        );
+    }
 }
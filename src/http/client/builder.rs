@@ -0,0 +1,260 @@
+use std::{sync::Arc, time::Duration};
+
+use http_client::isahc::IsahcClient;
+use isahc::{config::Configurable, http::Uri};
+use surf::{StatusCode, Url};
+
+use crate::ApiKeys;
+
+use super::{
+    exchange_info_cache::{self, ExchangeInfoCache},
+    rate_limiter::RateLimiter,
+    retry::RetryPolicy,
+    Client, UrlCache,
+};
+
+/** Used to construct a [`Client`] with optional settings.
+
+Get one by calling [`Client::builder`].
+# Examples
+## Limit the request rate
+```no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+# async_std::task::block_on(async {
+use std::time::Duration;
+
+use btcturk::Client;
+
+let client = Client::builder()
+    .rate_limit(10, Duration::from_secs(1))
+    .build()?;
+
+let ticker = client.ticker("BTCUSDT").await?;
+
+# Ok::<(), Box<dyn std::error::Error>>(())
+# })
+# }
+```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ClientBuilder {
+    keys: Option<ApiKeys>,
+    id: Option<String>,
+    base_url: Option<Url>,
+    http_client: Option<surf::Client>,
+    proxy: Option<Url>,
+    rate_limit: Option<(u32, Duration)>,
+    retry: Option<RetryPolicy>,
+    timeout: Option<Duration>,
+    recv_window: Option<Duration>,
+    dry_run: bool,
+    user_agent: Option<String>,
+    exchange_info_ttl: Option<Duration>,
+}
+
+impl ClientBuilder {
+    /// Construct a builder with the default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the API keys. See [`Client::new`].
+    #[must_use]
+    pub fn keys(mut self, keys: ApiKeys) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Set the client identifier. See [`Client::new`].
+    #[must_use]
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Derive the endpoints from the given base URL. See
+    /// [`Client::with_base_url`].
+    #[must_use]
+    pub fn base_url(mut self, base: Url) -> Self {
+        self.base_url = Some(base);
+        self
+    }
+
+    /// Send the requests through the given HTTP client. See
+    /// [`Client::with_http_client`]. Can't be combined with
+    /// [`proxy`][Self::proxy].
+    #[must_use]
+    pub fn http_client(mut self, http_client: surf::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Send the requests through the given proxy, e.g.
+    /// `http://proxy.example.com:8080`. Credentials can be given in the URL.
+    ///
+    /// The proxy is set on the HTTP client created by the builder. To use a
+    /// proxy with your own HTTP client, configure it on that client and pass
+    /// it to [`http_client`][Self::http_client] instead. Setting both makes
+    /// [`build`][Self::build] fail.
+    #[must_use]
+    pub fn proxy(mut self, url: Url) -> Self {
+        self.proxy = Some(url);
+        self
+    }
+
+    /// Send at most `requests` requests per `per` duration. Requests exceeding
+    /// the limit wait until the limit allows them instead of failing. Clones
+    /// of the built client share the same limit.
+    ///
+    /// See also <https://docs.btcturk.com/rate-limits>.
+    #[must_use]
+    pub const fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+        self.rate_limit = Some((requests, per));
+        self
+    }
+
+    /// Retry the `GET` requests which fail with `429 Too Many Requests` or
+    /// `503 Service Unavailable` up to `max_retries` times. The first retry
+    /// waits for `backoff` and each following retry waits twice as long as
+    /// the previous one unless the response has a `Retry-After` header.
+    /// A `Retry-After` longer than both the longest backoff and the
+    /// [`timeout`][Self::timeout] isn't waited for. The response is returned
+    /// as an error instead.
+    ///
+    /// Requests which submit or cancel orders are never retried to avoid
+    /// duplicate orders. If all retries fail, the last error is returned.
+    #[must_use]
+    pub const fn retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.retry = Some(RetryPolicy::new(max_retries, backoff));
+        self
+    }
+
+    /// Fail the requests which take longer than `timeout` with
+    /// [`SendRequest::Timeout`][crate::error::SendRequest::Timeout]. Sending
+    /// the request and receiving the whole response counts. Each retry gets
+    /// its own timeout. Defaults to 30 seconds.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on the signed requests which can't be sent within
+    /// `recv_window` after they are issued, e.g. because they wait for the
+    /// [`rate_limit`][Self::rate_limit] or a [`retry`][Self::retry], with
+    /// [`SendRequest::Expired`][crate::error::SendRequest::Expired]. This
+    /// keeps a stale order from being placed late on a slow link.
+    ///
+    /// Each attempt is signed right before it is sent with the local time
+    /// adjusted by the [`Client::clock_offset`]. It is sent only if that
+    /// timestamp is at most `recv_window` later than the adjusted time the
+    /// request was issued at, so a change of the offset in between counts.
+    /// The exchange doesn't take a validity window so this is only checked
+    /// locally. The time on the wire isn't counted and the server may still
+    /// receive a request later than the window, which the
+    /// [`timeout`][Self::timeout] bounds instead.
+    ///
+    /// [`Client::sync_clock`] fails if the local clock is off by more than
+    /// the window. Not set by default.
+    #[must_use]
+    pub const fn recv_window(mut self, recv_window: Duration) -> Self {
+        self.recv_window = Some(recv_window);
+        self
+    }
+
+    /// Only log the orders instead of sending them, e.g. to test a strategy
+    /// against the production endpoint without trading. Defaults to `false`.
+    ///
+    /// [`Client::submit`] and the methods using it return a [`NewOrder`]
+    /// built from the order with a negative identifier.
+    /// [`Client::cancel_order`] returns a successful result with the code
+    /// `0` and no message. Other requests are sent as usual.
+    ///
+    /// [`NewOrder`]: crate::http::private::NewOrder
+    #[must_use]
+    pub const fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the `User-Agent` header of the requests. Defaults to
+    /// `btcturk-rs/<version>`.
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Set how long [`Client::exchange_info_cached`] reuses a received
+    /// exchange info. Defaults to an hour.
+    #[must_use]
+    pub const fn exchange_info_ttl(mut self, ttl: Duration) -> Self {
+        self.exchange_info_ttl = Some(ttl);
+        self
+    }
+
+    /// Build the client.
+    /// # Errors
+    /// A [`surf`] error will occur if there is an error building an HTTP
+    /// client, both an HTTP client and a proxy are set or an endpoint URL
+    /// can't be derived from the base URL.
+    /// # Panics
+    /// If any of the hardcoded endpoint URLs can't be parsed, this function
+    /// will panic.
+    pub fn build(self) -> surf::Result<Client> {
+        let url_cache = match &self.base_url {
+            Some(base) => UrlCache::from_base(base)?,
+            None => UrlCache::new(),
+        };
+        let http_client = match (self.http_client, &self.proxy) {
+            (Some(_), Some(_)) => {
+                return Err(surf::Error::from_str(
+                    StatusCode::BadRequest,
+                    "set the proxy on the given HTTP client instead",
+                ))
+            }
+            (Some(http_client), None) => http_client,
+            (None, Some(proxy)) => proxied_http_client(proxy)?,
+            (None, None) => surf::Client::new(),
+        };
+        Ok(Client {
+            keys: self.keys,
+            id: self.id,
+            http_client,
+            url_cache,
+            rate_limiter: self.rate_limit.map(|(requests, per)| {
+                Arc::new(RateLimiter::new(requests, per))
+            }),
+            retry: self.retry,
+            timeout: self.timeout.unwrap_or(super::DEFAULT_TIMEOUT),
+            recv_window: self.recv_window,
+            dry_run: self.dry_run,
+            user_agent: self
+                .user_agent
+                .unwrap_or_else(|| super::DEFAULT_USER_AGENT.to_owned()),
+            last_rate_limit: Arc::default(),
+            last_latency: Arc::default(),
+            clock_offset: Arc::default(),
+            dry_run_id: Arc::default(),
+            exchange_info_cache: Arc::new(ExchangeInfoCache::new(
+                self.exchange_info_ttl
+                    .unwrap_or(exchange_info_cache::DEFAULT_TTL),
+            )),
+        })
+    }
+}
+
+/// Create an HTTP client which sends the requests through the given proxy.
+fn proxied_http_client(proxy: &Url) -> surf::Result<surf::Client> {
+    let proxy = proxy
+        .as_str()
+        .parse::<Uri>()
+        .map_err(|error| surf::Error::new(StatusCode::BadRequest, error))?;
+    let client = isahc::HttpClient::builder().proxy(proxy).build();
+    let client = client.map_err(|error| {
+        surf::Error::new(StatusCode::InternalServerError, error)
+    })?;
+    let client = IsahcClient::from_client(client);
+    Ok(surf::Client::with_http_client(client))
+}
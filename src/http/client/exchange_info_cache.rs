@@ -0,0 +1,45 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::http::public::ExchangeInfo;
+
+/// Time to live of the cached exchange info unless set otherwise.
+// `Duration::from_hours` needs Rust 1.91.
+#[allow(clippy::duration_suboptimal_units)]
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Last received exchange info and when it was received.
+#[derive(Debug)]
+pub struct ExchangeInfoCache {
+    ttl: Duration,
+    entry: Mutex<Option<(Instant, Arc<ExchangeInfo>)>>,
+}
+
+impl ExchangeInfoCache {
+    pub const fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// Get the cached exchange info unless it is older than the time to live.
+    pub fn get(&self) -> Option<Arc<ExchangeInfo>> {
+        self.entry
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|(received_at, _)| received_at.elapsed() < self.ttl)
+            .map(|(_, exchange_info)| Arc::clone(exchange_info))
+    }
+
+    /// Replace the cached exchange info.
+    pub fn set(&self, exchange_info: ExchangeInfo) -> Arc<ExchangeInfo> {
+        let exchange_info = Arc::new(exchange_info);
+        *self.entry.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((Instant::now(), Arc::clone(&exchange_info)));
+        exchange_info
+    }
+}
@@ -0,0 +1,120 @@
+use surf::StatusCode;
+
+use crate::{error::SendRequest, Client};
+
+/// Result of [`Client::health`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Health {
+    /// Whether the public endpoints answered.
+    pub reachable: bool,
+    /// Whether the API keys were accepted. `None` if the client has no keys
+    /// or the API is not [`reachable`][Self::reachable].
+    pub authenticated: Option<bool>,
+}
+
+impl Health {
+    /// Whether the API is reachable and the keys, if any, were accepted.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.reachable && self.authenticated != Some(false)
+    }
+}
+
+impl Client {
+    /// Check whether the API is reachable and, if the client has keys,
+    /// whether they are accepted. Nothing is changed on the account.
+    ///
+    /// The public check gets the [`server_time`][Self::server_time]. A
+    /// transport error or a status code other than 200 OK means the API is
+    /// not reachable. The private check gets the
+    /// [`account_balance`][Self::account_balance]. A status code of 401
+    /// Unauthorized or 403 Forbidden means the keys are rejected.
+    /// # Errors
+    /// [`SendRequest`] if any other error occurs, e.g. the received response
+    /// is malformed.
+    pub async fn health(&self) -> Result<Health, SendRequest> {
+        match self.server_time().await {
+            Ok(_) => {}
+            Err(
+                error @ (SendRequest::SurfError { .. }
+                | SendRequest::BadStatusCode { .. }),
+            ) => {
+                log::debug!("public endpoint is not reachable: {error}");
+                return Ok(Health {
+                    reachable: false,
+                    authenticated: None,
+                });
+            }
+            Err(error) => return Err(error),
+        }
+        if self.keys.is_none() {
+            return Ok(Health {
+                reachable: true,
+                authenticated: None,
+            });
+        }
+        let authenticated = match self.account_balance().await {
+            Ok(_) => true,
+            Err(
+                error @ SendRequest::BadStatusCode {
+                    status_code:
+                        StatusCode::Unauthorized | StatusCode::Forbidden,
+                    ..
+                },
+            ) => {
+                log::debug!("API keys are rejected: {error}");
+                false
+            }
+            Err(error) => return Err(error),
+        };
+        Ok(Health {
+            reachable: true,
+            authenticated: Some(authenticated),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use surf::StatusCode;
+
+    use super::Health;
+    use crate::{http::client::mock::MockHttpClient, Client};
+
+    #[async_std::test]
+    async fn check_health() {
+        let backend = MockHttpClient::new();
+        let client = Client::with_http_client(backend.client(), None, None);
+        let health = client.health().await.unwrap();
+        assert_eq!(
+            health,
+            Health {
+                reachable: false,
+                authenticated: None
+            }
+        );
+        assert!(!health.is_healthy());
+
+        let exchange_info = include_str!("../public/exchange_info/sample.json");
+        backend.push_data(exchange_info);
+        let health = client.health().await.unwrap();
+        assert_eq!(health.authenticated, None);
+        assert!(health.is_healthy());
+
+        let client = backend.client_with_keys();
+        backend
+            .push_data(exchange_info)
+            .push_data(include_str!("../private/account_balance/sample.json"));
+        assert_eq!(client.health().await.unwrap().authenticated, Some(true));
+
+        backend.push_data(exchange_info).push(
+            StatusCode::Unauthorized,
+            r#"{"success":false,"message":"Unauthorized","code":401}"#,
+        );
+        let health = client.health().await.unwrap();
+        assert_eq!(health.authenticated, Some(false));
+        assert!(!health.is_healthy());
+        assert_eq!(backend.requests().len(), 6);
+    }
+}
@@ -0,0 +1,117 @@
+//! HTTP backend which answers the requests with queued responses instead of
+//! sending them. Used in the tests.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use surf::{
+    http::{Method, Request, Response},
+    HttpClient, StatusCode, Url,
+};
+
+use crate::{ApiKeys, Client};
+
+/// Keys which are only good for signing the requests in the tests.
+pub fn keys() -> ApiKeys {
+    ApiKeys::new("63762e79-cb5c-4c0b-b714-5f0ce94bf100", "cHJpdmF0ZQ==")
+        .unwrap()
+}
+
+/// Parts of a request received by the mock backend.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockHttpClient {
+    responses: Arc<Mutex<VecDeque<Response>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockHttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response with the given status code and body.
+    pub fn push(&self, status_code: StatusCode, body: &str) -> &Self {
+        let mut response = Response::new(status_code);
+        response.set_body(body);
+        self.push_response(response)
+    }
+
+    /// Queue a successful response which wraps the given JSON data like the
+    /// API does.
+    pub fn push_data(&self, data: &str) -> &Self {
+        self.push(
+            StatusCode::Ok,
+            &format!(
+                r#"{{"data":{data},"success":true,"message":null,"code":0}}"#
+            ),
+        )
+    }
+
+    /// Queue the given response.
+    pub fn push_response(&self, response: Response) -> &Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Client which sends the requests through this backend.
+    pub fn client(&self) -> surf::Client {
+        surf::Client::with_http_client(self.clone())
+    }
+
+    /// Client with the test [`keys`] which sends the requests through this
+    /// backend.
+    pub fn client_with_keys(&self) -> Client {
+        Client::with_http_client(self.client(), Some(keys()), None)
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for MockHttpClient {
+    async fn send(
+        &self,
+        mut request: Request,
+    ) -> Result<Response, surf::Error> {
+        let recorded = RecordedRequest {
+            method: request.method(),
+            url: request.url().clone(),
+            headers: request
+                .iter()
+                .map(|(name, values)| {
+                    (name.to_string(), values.as_str().to_owned())
+                })
+                .collect(),
+            body: request.body_string().await?,
+        };
+        self.requests.lock().unwrap().push(recorded);
+        self.responses.lock().unwrap().pop_front().ok_or_else(|| {
+            surf::Error::from_str(
+                StatusCode::InternalServerError,
+                "no queued response",
+            )
+        })
+    }
+}
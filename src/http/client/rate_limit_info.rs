@@ -0,0 +1,36 @@
+/// Request budget reported by the server in the response headers.
+///
+/// A field is `None` if the server didn't send the corresponding header.
+///
+/// See also <https://docs.btcturk.com/rate-limits>.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RateLimitInfo {
+    /// Number of requests allowed in the current window. Read from the
+    /// `X-RateLimit-Limit` header.
+    pub limit: Option<u64>,
+    /// Number of requests left in the current window. Read from the
+    /// `X-RateLimit-Remaining` header.
+    pub remaining: Option<u64>,
+    /// When the current window resets, as sent by the server. Read from the
+    /// `X-RateLimit-Reset` header.
+    pub reset: Option<u64>,
+}
+
+impl RateLimitInfo {
+    /// Read the rate limit headers of a response. Returns `None` if none of
+    /// the headers are present.
+    #[must_use]
+    pub fn from_response(response: &surf::Response) -> Option<Self> {
+        let header = |name| {
+            response
+                .header(name)
+                .and_then(|value| value.as_str().trim().parse::<u64>().ok())
+        };
+        let info = Self {
+            limit: header("X-RateLimit-Limit"),
+            remaining: header("X-RateLimit-Remaining"),
+            reset: header("X-RateLimit-Reset"),
+        };
+        (info != Self::default()).then_some(info)
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use futures_timer::Delay;
+
+/// Token bucket which allows a burst of `capacity` requests and refills one
+/// token every `interval`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: u32,
+    interval: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: u32,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Allow `requests` requests per `per` duration. Zero requests are
+    /// treated as one.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        let capacity = requests.max(1);
+        Self {
+            capacity,
+            interval: per / capacity,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a token is available and take it.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            Delay::new(wait).await;
+        }
+    }
+
+    /// Take a token if there is one, otherwise return how long to wait for
+    /// the next one.
+    fn try_acquire(&self) -> Option<Duration> {
+        if self.interval.is_zero() {
+            return None;
+        }
+        let mut bucket =
+            self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = bucket.refilled_at.elapsed();
+        let refills = elapsed.as_nanos() / self.interval.as_nanos();
+        if refills > 0 {
+            let refills = u32::try_from(refills).unwrap_or(u32::MAX);
+            bucket.tokens = bucket.tokens.saturating_add(refills);
+            if bucket.tokens >= self.capacity {
+                bucket.tokens = self.capacity;
+                bucket.refilled_at = Instant::now();
+            } else {
+                bucket.refilled_at += self.interval * refills;
+            }
+        }
+        if bucket.tokens > 0 {
+            bucket.tokens -= 1;
+            None
+        } else {
+            Some(self.interval.saturating_sub(bucket.refilled_at.elapsed()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::RateLimiter;
+
+    #[async_std::test]
+    async fn wait_when_exhausted() {
+        let limiter = RateLimiter::new(2, Duration::from_millis(100));
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use surf::{http::Method, StatusCode};
+
+/// Decides whether and when a failed request is sent again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub const fn new(max_retries: u32, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+        }
+    }
+
+    /// Only idempotent requests are retried so that an order is never
+    /// submitted or canceled twice.
+    pub fn should_retry(
+        &self,
+        method: Method,
+        status_code: StatusCode,
+        retries: u32,
+    ) -> bool {
+        method == Method::Get
+            && retries < self.max_retries
+            && matches!(
+                status_code,
+                StatusCode::TooManyRequests | StatusCode::ServiceUnavailable
+            )
+    }
+
+    /// Wait as long as the `Retry-After` header says, if any. Otherwise, wait
+    /// twice as long as the previous retry.
+    ///
+    /// Returns `None` to give up if the header asks for longer than the
+    /// [`max_delay`][Self::max_delay] for the `timeout`.
+    pub fn delay(
+        &self,
+        retries: u32,
+        retry_after: Option<&str>,
+        timeout: Duration,
+    ) -> Option<Duration> {
+        retry_after
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map_or_else(
+                || {
+                    Some(
+                        self.backoff
+                            .saturating_mul(2_u32.saturating_pow(retries)),
+                    )
+                },
+                |seconds| {
+                    Some(Duration::from_secs(seconds))
+                        .filter(|delay| *delay <= self.max_delay(timeout))
+                },
+            )
+    }
+
+    /// Longest `Retry-After` which is waited for. It is the longest backoff
+    /// of the policy or the `timeout` of a request, whichever is longer.
+    pub fn max_delay(&self, timeout: Duration) -> Duration {
+        self.backoff
+            .saturating_mul(2_u32.saturating_pow(self.max_retries))
+            .max(timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use surf::{http::Method, StatusCode};
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn retry_only_get_requests() {
+        let policy = RetryPolicy::new(2, Duration::from_millis(10));
+        let status_code = StatusCode::TooManyRequests;
+        assert!(policy.should_retry(Method::Get, status_code, 1));
+        assert!(!policy.should_retry(Method::Get, status_code, 2));
+        assert!(!policy.should_retry(Method::Post, status_code, 0));
+        assert!(!policy.should_retry(Method::Delete, status_code, 0));
+        assert!(!policy.should_retry(Method::Get, StatusCode::BadRequest, 0));
+    }
+
+    #[test]
+    fn back_off_exponentially() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(10));
+        let timeout = Duration::from_secs(30);
+        assert_eq!(
+            policy.delay(0, None, timeout),
+            Some(Duration::from_millis(10))
+        );
+        assert_eq!(
+            policy.delay(2, None, timeout),
+            Some(Duration::from_millis(40))
+        );
+        assert_eq!(
+            policy.delay(2, Some("3"), timeout),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            policy.delay(1, Some("Wed, 21 Oct 2015 07:28:00 GMT"), timeout),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn cap_retry_after() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(10));
+        let timeout = Duration::from_secs(30);
+        assert_eq!(policy.max_delay(timeout), Duration::from_secs(80));
+        assert_eq!(
+            policy.delay(0, Some("80"), timeout),
+            Some(Duration::from_secs(80))
+        );
+        assert_eq!(policy.delay(0, Some("86400"), timeout), None);
+        assert_eq!(
+            policy.max_delay(Duration::from_secs(100)),
+            Duration::from_secs(100)
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Spans of the requests when the `tracing` feature is enabled.
+
+use std::{future::Future, time::Instant};
+
+use tracing::{field, Instrument};
+
+use crate::{error::SendRequest, http::Request};
+
+/// Run the exchange of the request in a `request` span. The span records the
+/// endpoint, the method and whether the request is authenticated. The HTTP
+/// status and the duration are recorded when the response is received.
+pub async fn traced(
+    request: &Request<'_>,
+    exchange: impl Future<Output = Result<String, SendRequest>> + Send,
+) -> Result<String, SendRequest> {
+    let span = tracing::info_span!(
+        "request",
+        endpoint = %request.endpoint,
+        method = %request.method,
+        auth = request.requires_auth,
+        status = field::Empty,
+        duration_ms = field::Empty,
+    );
+    let started = Instant::now();
+    let result = exchange.instrument(span.clone()).await;
+    let duration_ms = u64::try_from(started.elapsed().as_millis());
+    span.record("duration_ms", duration_ms.unwrap_or(u64::MAX));
+    let _entered = span.enter();
+    match &result {
+        Ok(response_string) => {
+            span.record("status", 200);
+            tracing::debug!(response = %response_string, "received response");
+        }
+        Err(SendRequest::BadStatusCode {
+            status_code,
+            response_string,
+            ..
+        }) => {
+            span.record("status", u16::from(*status_code));
+            tracing::warn!(
+                response = %response_string,
+                "received bad status code"
+            );
+        }
+        Err(error) => tracing::warn!(%error, "request failed"),
+    }
+    result
+}
@@ -16,12 +16,6 @@ macro_rules! base {
     };
 }
 
-macro_rules! endpoint {
-    ($endpoint:literal) => {
-        Url::parse(concat!(base!(), $endpoint)).expect(PARSE_FAILURE_MESSAGE)
-    };
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UrlCache {
     ticker: Url,
@@ -29,6 +23,7 @@ pub struct UrlCache {
     order_book: Url,
     trades: Url,
     ohlc: Url,
+    klines: Url,
     account_balance: Url,
     trade_transactions: Url,
     crypto_transactions: Url,
@@ -41,22 +36,8 @@ pub struct UrlCache {
 
 impl Default for UrlCache {
     fn default() -> Self {
-        Self {
-            ticker: endpoint!("api/v2/ticker"),
-            currency: endpoint!("api/v2/ticker/currency"),
-            order_book: endpoint!("api/v2/orderbook"),
-            trades: endpoint!("api/v2/trades"),
-            ohlc: Url::parse("https://graph-api.btcturk.com/v1/ohlcs")
-                .expect(PARSE_FAILURE_MESSAGE),
-            account_balance: endpoint!("api/v1/users/balances"),
-            trade_transactions: endpoint!("api/v1/users/transactions/trade"),
-            crypto_transactions: endpoint!("api/v1/users/transactions/crypto"),
-            fiat_transactions: endpoint!("api/v1/users/transactions/fiat"),
-            open_orders: endpoint!("api/v1/openOrders"),
-            all_orders: endpoint!("api/v1/allOrders"),
-            submit_cancel_order: endpoint!("api/v1/order"),
-            exchange_info: endpoint!("api/v2/server/exchangeinfo"),
-        }
+        Self::from_base(&Url::parse(base!()).expect(PARSE_FAILURE_MESSAGE))
+            .expect(PARSE_FAILURE_MESSAGE)
     }
 }
 
@@ -65,6 +46,37 @@ impl UrlCache {
         Self::default()
     }
 
+    /// Derive every endpoint from the given base URL. A trailing slash is
+    /// appended to the path of the base if it is missing so that the base
+    /// path is preserved. OHLC and kline endpoints are served from a
+    /// different host so they are not affected by the base.
+    pub fn from_base(base: &Url) -> Result<Self, url::ParseError> {
+        let mut base = base.clone();
+        if !base.path().ends_with('/') {
+            let path = format!("{}/", base.path());
+            base.set_path(&path);
+        }
+        Ok(Self {
+            ticker: base.join("api/v2/ticker")?,
+            currency: base.join("api/v2/ticker/currency")?,
+            order_book: base.join("api/v2/orderbook")?,
+            trades: base.join("api/v2/trades")?,
+            ohlc: Url::parse("https://graph-api.btcturk.com/v1/ohlcs")?,
+            klines: Url::parse(
+                "https://graph-api.btcturk.com/v1/klines/history",
+            )?,
+            account_balance: base.join("api/v1/users/balances")?,
+            trade_transactions: base.join("api/v1/users/transactions/trade")?,
+            crypto_transactions: base
+                .join("api/v1/users/transactions/crypto")?,
+            fiat_transactions: base.join("api/v1/users/transactions/fiat")?,
+            open_orders: base.join("api/v1/openOrders")?,
+            all_orders: base.join("api/v1/allOrders")?,
+            submit_cancel_order: base.join("api/v1/order")?,
+            exchange_info: base.join("api/v2/server/exchangeinfo")?,
+        })
+    }
+
     pub const fn ticker(&self) -> &Url {
         &self.ticker
     }
@@ -85,6 +97,10 @@ impl UrlCache {
         &self.ohlc
     }
 
+    pub const fn klines(&self) -> &Url {
+        &self.klines
+    }
+
     pub const fn account_balance(&self) -> &Url {
         &self.account_balance
     }
@@ -117,3 +133,25 @@ impl UrlCache {
         &self.exchange_info
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_str_eq;
+    use surf::Url;
+
+    use super::UrlCache;
+
+    #[test]
+    fn from_base() {
+        let base = Url::parse("http://localhost:8080/mock").unwrap();
+        let url_cache = UrlCache::from_base(&base).unwrap();
+        assert_str_eq!(
+            url_cache.ticker().as_str(),
+            "http://localhost:8080/mock/api/v2/ticker"
+        );
+        assert_str_eq!(
+            url_cache.submit_cancel_order().as_str(),
+            "http://localhost:8080/mock/api/v1/order"
+        );
+    }
+}
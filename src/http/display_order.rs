@@ -0,0 +1,64 @@
+use super::public::{exchange_info::Symbol, Ticker};
+
+/// Item of a pair which has the position the exchange lists the pair at.
+///
+/// The position is the `order` of the item. The pairs are listed in
+/// ascending order.
+pub trait DisplayOrder {
+    /// Get the position of the pair. Lower ones are listed first.
+    fn display_order(&self) -> i64;
+}
+
+impl DisplayOrder for Ticker {
+    fn display_order(&self) -> i64 {
+        i64::try_from(self.order).unwrap_or(i64::MAX)
+    }
+}
+
+impl DisplayOrder for Symbol {
+    fn display_order(&self) -> i64 {
+        self.order
+    }
+}
+
+/// Sort the items in the order the exchange lists their pairs.
+///
+/// Use it on the [`tickers`][super::Client::tickers] or the
+/// [`symbols`][super::public::ExchangeInfo::symbols] of the exchange info.
+/// The items with the same position keep the order they are in.
+pub fn sort_by_display_order<T: DisplayOrder>(items: &mut [T]) {
+    items.sort_by_key(DisplayOrder::display_order);
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::sort_by_display_order;
+    use crate::http::public::{ExchangeInfo, Ticker};
+
+    #[test]
+    fn sort_by_order() {
+        let json_string = include_str!("public/exchange_info/sample.json");
+        let mut symbols = serde_json::from_str::<ExchangeInfo>(json_string)
+            .unwrap()
+            .symbols;
+        symbols.reverse();
+        sort_by_display_order(&mut symbols);
+        let names = symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["BTCTRY", "USDTTRY", "XTZBTC"]);
+
+        let ticker = serde_json::from_str::<Ticker>(include_str!(
+            "public/ticker/sample.json"
+        ))
+        .unwrap();
+        let mut tickers = vec![ticker.clone(), ticker];
+        tickers[0].order = 3000;
+        tickers[0].pair = "ETHUSDT".to_owned();
+        sort_by_display_order(&mut tickers);
+        assert_eq!(tickers[0].pair, "BTCUSDT");
+    }
+}
@@ -8,15 +8,18 @@ mod request;
 pub(crate) use request::Request;
 
 mod response;
-pub(crate) use response::Response;
+pub(crate) use response::{empty_as_none, Data, Response, Single};
 
 pub mod private;
 pub mod public;
 
 mod client;
-pub use client::Client;
+#[cfg(test)]
+pub(crate) use client::mock;
+pub use client::{Client, ClientBuilder, Health, RateLimitInfo};
 
 mod api_keys;
+pub(crate) use api_keys::nonce;
 pub use api_keys::ApiKeys;
 
 mod order_type;
@@ -27,3 +30,9 @@ pub use order_method::OrderMethod;
 
 mod order_status;
 pub use order_status::OrderStatus;
+
+mod pair_symbol;
+pub use pair_symbol::PairSymbol;
+
+mod display_order;
+pub use display_order::{sort_by_display_order, DisplayOrder};
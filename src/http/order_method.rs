@@ -1,14 +1,12 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::error::Parse;
 
-#[allow(missing_docs)]
-#[derive(
-    Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-#[serde(try_from = "String")]
+/// Method of an order. A method which is not known by this crate is kept as
+/// [`Unknown`][Self::Unknown] when received from the API.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OrderMethod {
     #[allow(missing_docs)]
     Market,
@@ -18,8 +16,41 @@ pub enum OrderMethod {
     StopLimit,
     #[allow(missing_docs)]
     StopMarket,
+    /// Method which is not known by this crate. Holds the received string.
+    Unknown(String),
 }
 
+impl OrderMethod {
+    /// Get the received string if the method is
+    /// [`Unknown`][Self::Unknown].
+    #[must_use]
+    pub fn unknown_value(&self) -> Option<&str> {
+        match self {
+            Self::Unknown(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "market" | "Market" | "MARKET" => Self::Market,
+            "limit" | "Limit" | "LIMIT" => Self::Limit,
+            "stoplimit" | "stopLimit" | "StopLimit" | "STOP_LIMIT" => {
+                Self::StopLimit
+            }
+            "stopmarket" | "stopMarket" | "StopMarket" | "STOP_MARKET" => {
+                Self::StopMarket
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// Formats the method in lower case, e.g. `stoplimit`, which is how the
+/// `orderMethod` parameter of the submit order endpoint takes it. The API may
+/// send it in other cases, e.g. `stopLimit` or `Limit`, which are all parsed.
+///
+/// See also <https://docs.btcturk.com/private-endpoints/submit-order>.
 impl Display for OrderMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
@@ -27,6 +58,7 @@ impl Display for OrderMethod {
             OrderMethod::Limit => "limit",
             OrderMethod::StopLimit => "stoplimit",
             OrderMethod::StopMarket => "stopmarket",
+            Self::Unknown(value) => value,
         })
     }
 }
@@ -40,17 +72,59 @@ impl From<OrderMethod> for String {
 impl TryFrom<String> for OrderMethod {
     type Error = Parse;
 
+    /// Unlike the deserialization, unknown methods are rejected.
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        match value.as_ref() {
-            "market" | "Market" | "MARKET" => Ok(Self::Market),
-            "limit" | "Limit" | "LIMIT" => Ok(Self::Limit),
-            "stoplimit" | "stopLimit" | "StopLimit" | "STOP_LIMIT" => {
-                Ok(Self::StopLimit)
-            }
-            "stopmarket" | "stopMarket" | "StopMarket" | "STOP_MARKET" => {
-                Ok(Self::StopMarket)
-            }
-            other => Err(Parse::new(other, "&str", "OrderMethod")),
+        Self::parse(&value)
+            .ok_or_else(|| Parse::new(value, "&str", "OrderMethod"))
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderMethod {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Self::parse(&value).unwrap_or(Self::Unknown(value)))
+    }
+}
+
+impl FromStr for OrderMethod {
+    type Err = Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::OrderMethod;
+
+    #[test]
+    fn format_as_parameter() {
+        let methods = [
+            OrderMethod::Market,
+            OrderMethod::Limit,
+            OrderMethod::StopLimit,
+            OrderMethod::StopMarket,
+        ];
+        let strings = methods.clone().map(String::from);
+        assert_eq!(strings, ["market", "limit", "stoplimit", "stopmarket"]);
+        for (method, string) in methods.into_iter().zip(strings) {
+            assert_eq!(string.parse::<OrderMethod>().unwrap(), method);
         }
     }
+
+    #[test]
+    fn parse_from_str() {
+        assert_eq!(
+            "STOP_LIMIT".parse::<OrderMethod>().unwrap(),
+            OrderMethod::StopLimit
+        );
+        let error = "unknown".parse::<OrderMethod>().unwrap_err();
+        assert_eq!(error.source_content(), "unknown");
+        assert_eq!(error.destination_type(), "OrderMethod");
+    }
 }
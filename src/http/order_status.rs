@@ -1,14 +1,12 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::error::Parse;
 
-#[allow(missing_docs)]
-#[derive(
-    Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-#[serde(try_from = "String")]
+/// Status of an order. A status which is not known by this crate is kept as
+/// [`Unknown`][Self::Unknown] when received from the API.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OrderStatus {
     #[allow(missing_docs)]
     Canceled,
@@ -16,14 +14,48 @@ pub enum OrderStatus {
     Filled,
     #[allow(missing_docs)]
     Untouched,
+    /// Some of the order's quantity is filled.
+    PartiallyFilled,
+    /// Order is no longer on the book without being fully filled or canceled
+    /// by the user.
+    Closed,
+    /// Status which is not known by this crate. Holds the received string.
+    Unknown(String),
+}
+
+impl OrderStatus {
+    /// Get the received string if the status is
+    /// [`Unknown`][Self::Unknown].
+    #[must_use]
+    pub fn unknown_value(&self) -> Option<&str> {
+        match self {
+            Self::Unknown(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "canceled" | "Canceled" | "CANCELED" => Self::Canceled,
+            "filled" | "Filled" | "FILLED" => Self::Filled,
+            "untouched" | "Untouched" | "UNTOUCHED" => Self::Untouched,
+            "partial" | "Partial" | "PARTIAL" | "partiallyFilled"
+            | "PartiallyFilled" | "PARTIALLY_FILLED" => Self::PartiallyFilled,
+            "closed" | "Closed" | "CLOSED" => Self::Closed,
+            _ => return None,
+        })
+    }
 }
 
 impl Display for OrderStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
-            OrderStatus::Canceled => "Canceled",
-            OrderStatus::Filled => "Filled",
-            OrderStatus::Untouched => "Untouched",
+            Self::Canceled => "Canceled",
+            Self::Filled => "Filled",
+            Self::Untouched => "Untouched",
+            Self::PartiallyFilled => "Partial",
+            Self::Closed => "Closed",
+            Self::Unknown(value) => value,
         })
     }
 }
@@ -31,12 +63,44 @@ impl Display for OrderStatus {
 impl TryFrom<String> for OrderStatus {
     type Error = Parse;
 
+    /// Unlike the deserialization, unknown statuses are rejected.
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        match value.as_ref() {
-            "canceled" | "Canceled" | "CANCELED" => Ok(Self::Canceled),
-            "filled" | "Filled" | "FILLED" => Ok(Self::Filled),
-            "untouched" | "Untouched" | "UNTOUCHED" => Ok(Self::Untouched),
-            other => Err(Parse::new(other, "&str", "OrderStatus")),
-        }
+        Self::parse(&value)
+            .ok_or_else(|| Parse::new(value, "&str", "OrderStatus"))
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderStatus {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Self::parse(&value).unwrap_or(Self::Unknown(value)))
+    }
+}
+
+impl FromStr for OrderStatus {
+    type Err = Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::OrderStatus;
+
+    #[test]
+    fn parse_from_str() {
+        assert_eq!(
+            "partial".parse::<OrderStatus>().unwrap(),
+            OrderStatus::PartiallyFilled
+        );
+        let error = "unknown".parse::<OrderStatus>().unwrap_err();
+        assert_eq!(error.source_content(), "unknown");
+        assert_eq!(error.destination_type(), "OrderStatus");
     }
 }
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use serde::Deserialize;
 
@@ -16,6 +16,8 @@ pub enum OrderType {
     Sell,
 }
 
+/// Formats the type in lower case, e.g. `buy`, which is how the `orderType`
+/// parameter of the submit order endpoint takes it.
 impl Display for OrderType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
@@ -42,3 +44,32 @@ impl TryFrom<String> for OrderType {
         }
     }
 }
+
+impl FromStr for OrderType {
+    type Err = Parse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::OrderType;
+
+    #[test]
+    fn format_as_parameter() {
+        assert_eq!(String::from(OrderType::Buy), "buy");
+        assert_eq!(String::from(OrderType::Sell), "sell");
+    }
+
+    #[test]
+    fn parse_from_str() {
+        assert_eq!("sell".parse::<OrderType>().unwrap(), OrderType::Sell);
+        let error = "unknown".parse::<OrderType>().unwrap_err();
+        assert_eq!(error.source_content(), "unknown");
+        assert_eq!(error.destination_type(), "OrderType");
+    }
+}
@@ -0,0 +1,119 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::Parse;
+
+/// Symbol of a pair with its numerator and denominator kept apart, e.g.
+/// `BTC` and `USDT` for `BTCUSDT`. Both are in upper case.
+///
+/// It can be passed to every method which takes a pair symbol as a string
+/// since it converts to the concatenated form. Parsing it from a string
+/// catches the typos before a request is sent.
+/// ```
+/// use btcturk::http::PairSymbol;
+///
+/// let pair = "btcusdt".parse::<PairSymbol>().unwrap();
+/// assert_eq!(pair, PairSymbol::new("BTC", "USDT"));
+/// assert_eq!(pair.to_string(), "BTCUSDT");
+/// assert!("BTCUSD".parse::<PairSymbol>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PairSymbol {
+    numerator: String,
+    denominator: String,
+}
+
+impl PairSymbol {
+    /// Denominators of the pairs traded on the exchange, e.g. `USDT` for
+    /// `BTCUSDT`.
+    pub const DENOMINATORS: [&'static str; 5] =
+        ["TRY", "USDT", "USDC", "BTC", "EUR"];
+
+    /// Construct a pair symbol from its numerator and denominator. They are
+    /// converted to upper case but not validated.
+    #[must_use]
+    pub fn new(
+        numerator: impl Into<String>,
+        denominator: impl Into<String>,
+    ) -> Self {
+        Self {
+            numerator: numerator.into().to_uppercase(),
+            denominator: denominator.into().to_uppercase(),
+        }
+    }
+
+    /// Get the numerator, e.g. `BTC` for `BTCUSDT`.
+    #[must_use]
+    pub fn numerator(&self) -> &str {
+        &self.numerator
+    }
+
+    /// Get the denominator, e.g. `USDT` for `BTCUSDT`.
+    #[must_use]
+    pub fn denominator(&self) -> &str {
+        &self.denominator
+    }
+}
+
+impl Display for PairSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.numerator, self.denominator)
+    }
+}
+
+impl From<PairSymbol> for String {
+    fn from(value: PairSymbol) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for PairSymbol {
+    type Err = Parse;
+
+    /// Parse the concatenated form, e.g. `BTCUSDT`, by splitting one of the
+    /// [`DENOMINATORS`][Self::DENOMINATORS] off its end, or the normalized
+    /// form, e.g. `BTC_USDT`. The case doesn't matter.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let symbol = s.to_uppercase();
+        let (numerator, denominator) =
+            symbol.split_once('_').unwrap_or_else(|| {
+                Self::DENOMINATORS
+                    .iter()
+                    .find_map(|denominator| {
+                        Some((symbol.strip_suffix(denominator)?, *denominator))
+                    })
+                    .unwrap_or_default()
+            });
+        let valid = |part: &str| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric())
+        };
+        if valid(numerator) && valid(denominator) {
+            Ok(Self::new(numerator, denominator))
+        } else {
+            Err(Parse::new(s, "&str", "PairSymbol"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_str_eq};
+
+    use super::PairSymbol;
+
+    #[test]
+    fn parse_from_str() {
+        let pair = "ethbtc".parse::<PairSymbol>().unwrap();
+        assert_str_eq!(pair.numerator(), "ETH");
+        assert_str_eq!(pair.denominator(), "BTC");
+        assert_eq!(
+            "BTC_TRY".parse::<PairSymbol>().unwrap(),
+            PairSymbol::new("btc", "try")
+        );
+        assert_str_eq!(String::from(PairSymbol::new("xrp", "usdt")), "XRPUSDT");
+        for invalid in ["USDT", "BTCUSD", "_TRY", "BTC_", "BTC-TRY", ""] {
+            let error = invalid.parse::<PairSymbol>().unwrap_err();
+            assert_eq!(error.source_content(), invalid);
+            assert_eq!(error.destination_type(), "PairSymbol");
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Implementation of the account balance endpoint.
 
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
 use surf::http::Method;
 
@@ -9,7 +11,7 @@ use crate::{
     Client,
 };
 
-impl Client<'_> {
+impl Client {
     /// Retrieve all cash balances.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
@@ -30,6 +32,81 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Retrieve the balance of a single asset. The case of the asset is
+    /// ignored.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `asset`: For example, `BTC`.
+    pub async fn balance_of(
+        &self,
+        asset: &str,
+    ) -> Result<Option<AssetBalance>, SendRequest> {
+        Ok(self
+            .account_balance()
+            .await?
+            .into_iter()
+            .find(|balance| balance.asset.eq_ignore_ascii_case(asset)))
+    }
+
+    /// Retrieve the balances of the given assets only. The case of the
+    /// assets is ignored. The balances are in the order the API returns them
+    /// and the unknown assets are skipped.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `assets`: For example, `["BTC", "TRY"]`.
+    pub async fn account_balance_for(
+        &self,
+        assets: &[&str],
+    ) -> Result<Vec<AssetBalance>, SendRequest> {
+        Ok(self
+            .account_balance()
+            .await?
+            .into_iter()
+            .filter(|balance| {
+                assets
+                    .iter()
+                    .any(|asset| balance.asset.eq_ignore_ascii_case(asset))
+            })
+            .collect())
+    }
+
+    /// Same as [`account_balance`][Self::account_balance] but only the
+    /// assets whose [`total`][AssetBalance::total] is greater than zero are
+    /// kept.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    pub async fn nonzero_balances(
+        &self,
+    ) -> Result<Vec<AssetBalance>, SendRequest> {
+        Ok(self
+            .account_balance()
+            .await?
+            .into_iter()
+            .filter(|balance| balance.total() > Decimal::ZERO)
+            .collect())
+    }
+
+    /// Same as [`account_balance`][Self::account_balance] but the balances
+    /// are keyed by their [`asset`][AssetBalance::asset], e.g. `BTC`.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    pub async fn balances_map(
+        &self,
+    ) -> Result<HashMap<String, AssetBalance>, SendRequest> {
+        Ok(self
+            .account_balance()
+            .await?
+            .into_iter()
+            .map(|balance| (balance.asset.clone(), balance))
+            .collect())
+    }
 }
 
 /// **Sample**:
@@ -41,6 +118,7 @@ impl Client<'_> {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AssetBalance {
     #[allow(missing_docs)]
     pub asset: String,
@@ -55,9 +133,21 @@ pub struct AssetBalance {
     pub free: Decimal,
 }
 
+impl AssetBalance {
+    /// Get the sum of the [`free`][Self::free] and the
+    /// [`locked`][Self::locked] amounts. It is expected to be equal to the
+    /// [`balance`][Self::balance]. The sum saturates instead of overflowing.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        self.free.saturating_add(self.locked)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ApiKeys, Client};
+    use pretty_assertions::assert_eq;
+
+    use crate::{http::mock::MockHttpClient, ApiKeys, Client};
 
     use super::AssetBalance;
 
@@ -74,7 +164,7 @@ mod tests {
             .await
             .unwrap();
         for asset in assets {
-            assert!(asset.balance >= asset.free + asset.locked);
+            assert!(asset.balance >= asset.total());
         }
     }
 
@@ -83,4 +173,49 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<Vec<AssetBalance>>(json_string).unwrap();
     }
+
+    #[async_std::test]
+    async fn find_balances() {
+        let backend = MockHttpClient::new();
+        backend
+            .push_data(include_str!("sample.json"))
+            .push_data(include_str!("sample.json"))
+            .push_data(include_str!("sample.json"))
+            .push_data(include_str!("sample.json"));
+        let client = backend.client_with_keys();
+
+        let btc = client.balance_of("btc").await.unwrap().unwrap();
+        assert_eq!(btc.asset, "BTC");
+        assert!(client.balance_of("UNKNOWN").await.unwrap().is_none());
+
+        let balances = client
+            .account_balance_for(&["btc", "UNKNOWN"])
+            .await
+            .unwrap();
+        assert_eq!(balances, vec![btc]);
+
+        let balances = client.balances_map().await.unwrap();
+        let lira = &balances["TRY"];
+        assert_eq!(lira.total(), lira.balance);
+    }
+
+    #[async_std::test]
+    async fn filter_zero_balances() {
+        let backend = MockHttpClient::new();
+        backend.push_data(&include_str!("sample.json").replacen(
+            '[',
+            r#"[{"asset": "ETH", "assetname": "Ethereum", "balance": "0",
+                "locked": "0", "free": "0.0000000000000000"},"#,
+            1,
+        ));
+        let client = backend.client_with_keys();
+        let assets = client
+            .nonzero_balances()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|balance| balance.asset)
+            .collect::<Vec<_>>();
+        assert_eq!(assets, vec!["TRY", "BTC"]);
+    }
 }
@@ -1,6 +1,6 @@
 //! Implementation of the all orders endpoint.
 
-use std::ops::Range;
+use std::{ops::RangeBounds, time::SystemTime};
 
 use rust_decimal::Decimal;
 use serde::Deserialize;
@@ -8,11 +8,11 @@ use surf::http::Method;
 
 use crate::{
     error::{Parameter, SendRequest},
-    http::{request::Parameters, OrderMethod, OrderStatus, Request},
+    http::{request::Parameters, OrderMethod, OrderStatus, OrderType, Request},
     Client,
 };
 
-impl Client<'_> {
+impl Client {
     /// Retrieve all orders of any status.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
@@ -21,7 +21,8 @@ impl Client<'_> {
     /// - `order_id`: If orderId set, it will return all orders greater than or
     /// equals to this order id.
     /// - `pair_symbol`: For example, `BTCTRY`.
-    /// - `time_range`: Start-end date timestamp range.
+    /// - `time_range`: Start-end date timestamp range. An unbounded side is
+    ///   left out, e.g. pass `..` to send neither.
     /// - `page`: Page number.
     /// - `limit`: Default **100**, max **1000**.
     ///
@@ -30,23 +31,22 @@ impl Client<'_> {
         &self,
         order_id: Option<i64>,
         pair_symbol: impl Into<String> + Send,
-        time_range: Option<Range<u64>>,
+        time_range: impl RangeBounds<u64> + Send,
         page: Option<u64>,
         limit: Option<u16>,
     ) -> Result<Vec<Order>, SendRequest> {
         let mut parameters = Parameters::new();
         parameters.push_number("orderId", order_id);
         parameters.push_string("pairSymbol", Some(pair_symbol.into()));
-        if let Some(range) = time_range {
-            parameters.push_number("startTime", Some(range.start));
-            parameters.push_number("endTime", Some(range.end));
-        }
+        parameters.push_range(
+            "time_range",
+            ("startTime", "endTime"),
+            time_range,
+        )?;
         parameters.push_number("page", page);
         if let Some(limit) = limit {
             if limit > 1000 {
-                return Err(
-                    Parameter::new("limit", limit.to_string()).into()
-                );
+                return Err(Parameter::new("limit", limit.to_string()).into());
             }
             parameters.push_number("limit", Some(limit));
         }
@@ -61,6 +61,30 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Retrieve a single order of any status by its id. Useful for polling
+    /// the status of a submitted order.
+    ///
+    /// The all orders endpoint requires a pair symbol so it has to be passed
+    /// along with the id.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `id`: Id of the order.
+    /// - `pair_symbol`: Pair of the order. For example, `BTCTRY`.
+    ///
+    /// See also <https://docs.btcturk.com/private-endpoints/all-orders>.
+    pub async fn order(
+        &self,
+        id: i64,
+        pair_symbol: impl Into<String> + Send,
+    ) -> Result<Option<Order>, SendRequest> {
+        let orders = self
+            .all_orders(Some(id), pair_symbol, .., None, None)
+            .await?;
+        Ok(orders.into_iter().find(|order| order.id == id))
+    }
 }
 
 /// **Sample**:
@@ -70,6 +94,7 @@ impl Client<'_> {
 /// See also <https://docs.btcturk.com/private-endpoints/all-orders>
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Order {
     #[allow(missing_docs)]
     pub id: i64,
@@ -84,7 +109,7 @@ pub struct Order {
     #[allow(missing_docs)]
     pub pair_symbol_normalized: String,
     #[allow(missing_docs)]
-    pub r#type: String,
+    pub r#type: OrderType,
     #[allow(missing_docs)]
     pub method: OrderMethod,
     #[allow(missing_docs)]
@@ -97,10 +122,44 @@ pub struct Order {
     pub status: OrderStatus,
 }
 
+impl Order {
+    /// Get [`time`][Self::time] as a system time.
+    #[must_use]
+    pub fn time_as_system_time(&self) -> SystemTime {
+        crate::system_time::from_millis(self.time)
+    }
+
+    /// Get [`update_time`][Self::update_time] as a system time.
+    #[must_use]
+    pub fn update_time_as_system_time(&self) -> SystemTime {
+        crate::system_time::from_millis(self.update_time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Order {
+    /// Get [`time`][Self::time] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.time)
+    }
+
+    /// Get [`update_time`][Self::update_time] as a date and time in UTC.
+    #[must_use]
+    pub fn update_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.update_time)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ApiKeys, Client};
-    use pretty_assertions::assert_str_eq;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{
+        http::{mock::MockHttpClient, OrderMethod, OrderStatus, OrderType},
+        ApiKeys, Client,
+    };
+    use pretty_assertions::{assert_eq, assert_str_eq};
 
     use super::Order;
 
@@ -113,7 +172,7 @@ mod tests {
 
         let orders = Client::new(Some(keys), None)
             .unwrap()
-            .all_orders(None, "XRPUSDT", None, None, None)
+            .all_orders(None, "XRPUSDT", .., None, None)
             .await
             .unwrap();
         for order in orders {
@@ -121,9 +180,64 @@ mod tests {
         }
     }
 
+    #[async_std::test]
+    async fn find_order_by_id() {
+        let backend = MockHttpClient::new();
+        for _ in 0..2 {
+            backend.push_data(include_str!("sample.json"));
+        }
+        let client = backend.client_with_keys();
+        let order = client.order(9_932_534, "BTCTRY").await.unwrap();
+        assert_eq!(order.map(|order| order.id), Some(9_932_534));
+        assert_eq!(client.order(9_932_533, "BTCTRY").await.unwrap(), None);
+        assert_eq!(
+            backend.requests()[0].url.query(),
+            Some("orderId=9932534&pairSymbol=BTCTRY")
+        );
+    }
+
     #[test]
     fn deserialize_all_orders() {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<Vec<Order>>(json_string).unwrap();
     }
+
+    #[test]
+    fn convert_times_to_system_time() {
+        let json_string = include_str!("sample.json");
+        let orders = serde_json::from_str::<Vec<Order>>(json_string).unwrap();
+        let expected = UNIX_EPOCH + Duration::from_millis(1_543_996_112_263);
+        assert_eq!(orders[0].time_as_system_time(), expected);
+        assert_eq!(orders[0].update_time_as_system_time(), expected);
+    }
+
+    #[test]
+    fn deserialize_partial_order() {
+        let json_string = include_str!("sample.json")
+            .replace(r#""status": "Untouched""#, r#""status": "Partial""#);
+        let orders = serde_json::from_str::<Vec<Order>>(&json_string).unwrap();
+        assert_eq!(orders[0].status, OrderStatus::PartiallyFilled);
+    }
+
+    #[test]
+    fn order_type_round_trip() {
+        let json_string = include_str!("sample.json");
+        let orders = serde_json::from_str::<Vec<Order>>(json_string).unwrap();
+        assert_eq!(orders[0].r#type, OrderType::Buy);
+        let string = String::from(orders[0].r#type);
+        assert_eq!(OrderType::try_from(string).unwrap(), OrderType::Buy);
+    }
+
+    #[test]
+    fn keep_unknown_method_and_status() {
+        let json_string = include_str!("sample.json")
+            .replace(r#""method": "Limit""#, r#""method": "Iceberg""#)
+            .replace(r#""status": "Untouched""#, r#""status": "Expired""#);
+        let orders = serde_json::from_str::<Vec<Order>>(&json_string).unwrap();
+        assert_eq!(orders[0].method.unknown_value(), Some("Iceberg"));
+        assert_eq!(orders[0].status.unknown_value(), Some("Expired"));
+        assert_eq!(orders[0].status.to_string(), "Expired");
+        assert_eq!(OrderStatus::Filled.unknown_value(), None);
+        assert!(OrderMethod::try_from("Iceberg".to_owned()).is_err());
+    }
 }
@@ -1,16 +1,23 @@
-//! Implementation of the cancel order endpoint.
+//! Implementation of the cancel order endpoint and its helper methods.
 
-use serde::Deserialize;
+use futures::{stream, StreamExt};
 use surf::http::Method;
 
 use crate::{
     error::SendRequest,
-    http::{request::Parameters, Request},
+    http::{
+        private::{NewOrder, OrderSpec},
+        request::Parameters,
+        Request,
+    },
     Client,
 };
 
-impl Client<'_> {
+impl Client {
     /// Cancel an order.
+    ///
+    /// Returns the code and the message the server responded with, e.g.
+    /// `"SUCCESS"`.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
     /// is an error or a malformation in the received response.
@@ -18,30 +25,135 @@ impl Client<'_> {
     /// - `id`: Identifier of the order.
     ///
     /// See also <https://docs.btcturk.com/private-endpoints/cancel-order>.
-    pub async fn cancel_order(&self, id: i64) -> Result<(), SendRequest> {
+    pub async fn cancel_order(
+        &self,
+        id: i64,
+    ) -> Result<CancelResult, SendRequest> {
+        if self.is_dry_run() {
+            log::info!("dry run: cancel order {id}");
+            return Ok(CancelResult {
+                id,
+                code: 0,
+                message: None,
+            });
+        }
         let mut parameters = Parameters::new();
         parameters.push_number("id", Some(id));
-        self.send::<EmptyResponse>(
-            Request {
+        let (code, message) = self
+            .send_without_data(Request {
                 endpoint: self.url_cache().submit_cancel_order(),
                 method: Method::Delete,
                 parameters,
                 requires_auth: true,
-            },
-            false,
-        )
-        .await?;
-        Ok(())
+            })
+            .await?;
+        Ok(CancelResult { id, code, message })
+    }
+
+    /// Cancel the orders with the given identifiers. At most `concurrency`
+    /// cancellations are in flight at a time. Use
+    /// [`rate_limit`][crate::http::ClientBuilder::rate_limit] to space them
+    /// out further.
+    ///
+    /// Returns the result of each cancellation in the order of the
+    /// identifiers. A failed cancellation doesn't stop the others.
+    /// # Parameters
+    /// - `ids`: Identifiers of the orders.
+    /// - `concurrency`: Maximum number of concurrent requests. `0` is treated
+    ///   as `1`.
+    pub async fn cancel_orders(
+        &self,
+        ids: &[i64],
+        concurrency: usize,
+    ) -> Vec<(i64, Result<CancelResult, SendRequest>)> {
+        stream::iter(ids.iter().copied())
+            .map(|id| async move { (id, self.cancel_order(id).await) })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Cancel an order and submit another one in its place, e.g. to move an
+    /// order to a different price.
+    ///
+    /// The new order is submitted only after the old one is canceled. The
+    /// outcome tells whether the cancellation happened when the submission
+    /// fails, in which case neither order is open.
+    /// # Parameters
+    /// - `old_id`: Identifier of the order to cancel.
+    /// - `new_spec`: Order to submit in its place.
+    pub async fn replace_order(
+        &self,
+        old_id: i64,
+        new_spec: OrderSpec,
+    ) -> ReplaceOutcome {
+        let canceled = match self.cancel_order(old_id).await {
+            Ok(canceled) => canceled,
+            Err(error) => return ReplaceOutcome::CancelFailed(error),
+        };
+        match self.submit(new_spec).await {
+            Ok(new_order) => ReplaceOutcome::Replaced(new_order),
+            Err(error) => {
+                log::warn!(
+                    "order {old_id} is canceled but its replacement failed: \
+                    {error}"
+                );
+                ReplaceOutcome::SubmitFailed { canceled, error }
+            }
+        }
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct EmptyResponse;
+/// Result of [`replace_order`][Client::replace_order].
+#[derive(Debug)]
+pub enum ReplaceOutcome {
+    /// Old order is canceled and the new one is placed.
+    Replaced(NewOrder),
+    /// Old order couldn't be canceled so the new one is not submitted. The
+    /// old order may still be open.
+    CancelFailed(SendRequest),
+    /// Old order is canceled but the new one couldn't be submitted.
+    SubmitFailed {
+        /// Result of the cancellation.
+        canceled: CancelResult,
+        /// Error of the submission.
+        error: SendRequest,
+    },
+}
+
+impl ReplaceOutcome {
+    /// Whether the old order is canceled.
+    #[must_use]
+    pub const fn is_canceled(&self) -> bool {
+        !matches!(self, Self::CancelFailed(_))
+    }
+}
+
+/// Result of a successful [`cancel_order`][Client::cancel_order].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CancelResult {
+    /// Identifier of the canceled order.
+    pub id: i64,
+    /// Code of the response, e.g. `0`.
+    pub code: i64,
+    /// Message of the response, e.g. `"SUCCESS"`.
+    pub message: Option<String>,
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::{error::SendRequest, ApiKeys, Client};
+    use super::{CancelResult, ReplaceOutcome};
+    use crate::{
+        error::SendRequest,
+        http::{
+            mock::MockHttpClient, private::OrderSpec, OrderMethod, OrderType,
+        },
+        ApiKeys, Client,
+    };
     use log::info;
+    use pretty_assertions::assert_eq;
+    use rust_decimal::Decimal;
+    use surf::StatusCode;
 
     #[ignore]
     #[async_std::test]
@@ -55,12 +167,91 @@ mod tests {
             .cancel_order(7218394218)
             .await;
         info!("result is {:?}", result);
-        match result {
-            Err(ref err) => match err {
-                SendRequest::SerdeJsonError { source: _ } => result.unwrap(),
-                _ => (),
-            },
-            _ => (),
-        }
+        assert!(!matches!(result, Err(SendRequest::DeserializeError { .. })));
+    }
+
+    #[async_std::test]
+    async fn return_cancel_result() {
+        let backend = MockHttpClient::new();
+        backend.push(
+            StatusCode::Ok,
+            r#"{"success":true,"message":"SUCCESS","code":0}"#,
+        );
+        let client = backend.client_with_keys();
+        let result = client.cancel_order(1).await.unwrap();
+        assert_eq!(
+            result,
+            CancelResult {
+                id: 1,
+                code: 0,
+                message: Some("SUCCESS".to_owned()),
+            }
+        );
+    }
+
+    #[async_std::test]
+    async fn cancel_orders_one_by_one() {
+        let backend = MockHttpClient::new();
+        backend.push(StatusCode::NotFound, "").push(
+            StatusCode::Ok,
+            r#"{"success":false,"message":"FAILED","code":1}"#,
+        );
+        let client = backend.client_with_keys();
+        let results = client.cancel_orders(&[1, 2], 0).await;
+        let ids = results.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2]);
+        assert!(matches!(
+            results[0].1,
+            Err(SendRequest::BadStatusCode {
+                status_code: StatusCode::NotFound,
+                ..
+            })
+        ));
+        assert!(matches!(
+            results[1].1,
+            Err(SendRequest::ResponseError { .. })
+        ));
+        let requests = backend.requests();
+        assert_eq!(requests[0].url.query(), Some("id=1"));
+        assert_eq!(requests[1].url.query(), Some("id=2"));
+    }
+
+    #[async_std::test]
+    async fn replace_order() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        let spec = OrderSpec::new("BTCTRY", OrderType::Buy, OrderMethod::Limit)
+            .price(Decimal::ONE)
+            .quantity(Decimal::ONE);
+        let success = r#"{"success":true,"message":"SUCCESS","code":0}"#;
+
+        backend
+            .push(StatusCode::Ok, success)
+            .push_data(include_str!("../submit_order/sample.json"));
+        let outcome = client.replace_order(1, spec.clone()).await;
+        let ReplaceOutcome::Replaced(new_order) = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(new_order.id, 9_932_534);
+
+        backend.push(StatusCode::NotFound, "");
+        let outcome = client.replace_order(2, spec.clone()).await;
+        assert!(!outcome.is_canceled());
+        assert!(matches!(outcome, ReplaceOutcome::CancelFailed(_)));
+
+        backend
+            .push(StatusCode::Ok, success)
+            .push(StatusCode::BadRequest, "");
+        let outcome = client.replace_order(3, spec).await;
+        assert!(outcome.is_canceled());
+        let ReplaceOutcome::SubmitFailed { canceled, .. } = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(canceled.id, 3);
+
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].url.query(), Some("id=1"));
+        assert_eq!(requests[2].url.query(), Some("id=2"));
     }
 }
@@ -15,6 +15,7 @@ pub mod all_orders;
 pub use all_orders::Order;
 
 pub mod submit_order;
-pub use submit_order::NewOrder;
+pub use submit_order::{NewOrder, OrderSpec, SubmitOutcome};
 
 pub mod cancel_order;
+pub use cancel_order::{CancelResult, ReplaceOutcome};
@@ -1,16 +1,20 @@
 //! Implementation of the open orders endpoint.
 
+use std::{iter::Chain, slice, vec};
+
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use surf::http::Method;
 
 use crate::{
     error::SendRequest,
-    http::{request::Parameters, OrderMethod, OrderType, Request},
+    http::{
+        request::Parameters, Data, OrderMethod, OrderStatus, OrderType, Request,
+    },
     Client,
 };
 
-impl Client<'_> {
+impl Client {
     /// List your current open orders. Only open or un-settled orders are
     /// returned by default. As soon as an order is no longer open and settled,
     /// it will no longer appear in the default request. Open orders may change
@@ -49,6 +53,7 @@ impl Client<'_> {
 /// See also <https://docs.btcturk.com/private-endpoints/open-orders>
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct OpenOrders {
     #[allow(missing_docs)]
     pub asks: Vec<BidAsk>,
@@ -56,9 +61,44 @@ pub struct OpenOrders {
     pub bids: Vec<BidAsk>,
 }
 
+impl Data for OpenOrders {}
+
+impl OpenOrders {
+    /// Iterate over the asks and then the bids.
+    pub fn all(
+        &self,
+    ) -> Chain<slice::Iter<'_, BidAsk>, slice::Iter<'_, BidAsk>> {
+        self.asks.iter().chain(self.bids.iter())
+    }
+
+    /// Same as [`all`][Self::all] but takes the orders.
+    pub fn into_all(
+        self,
+    ) -> Chain<vec::IntoIter<BidAsk>, vec::IntoIter<BidAsk>> {
+        self.asks.into_iter().chain(self.bids)
+    }
+
+    /// Find the order with the given identifier on either side.
+    #[must_use]
+    pub fn find(&self, id: i64) -> Option<&BidAsk> {
+        self.all().find(|order| order.id == id)
+    }
+}
+
+/// Iterates over the asks and then the bids.
+impl IntoIterator for OpenOrders {
+    type Item = BidAsk;
+    type IntoIter = Chain<vec::IntoIter<BidAsk>, vec::IntoIter<BidAsk>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_all()
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct BidAsk {
     #[allow(missing_docs)]
     pub id: i64,
@@ -85,16 +125,31 @@ pub struct BidAsk {
     #[allow(missing_docs)]
     pub update_time: u64,
     #[allow(missing_docs)]
-    pub status: String,
+    pub status: OrderStatus,
     #[allow(missing_docs)]
     pub left_amount: Decimal,
 }
 
+#[cfg(feature = "chrono")]
+impl BidAsk {
+    /// Get [`time`][Self::time] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.time)
+    }
+
+    /// Get [`update_time`][Self::update_time] as a date and time in UTC.
+    #[must_use]
+    pub fn update_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.update_time)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::OpenOrders;
-    use crate::{ApiKeys, Client};
-    use pretty_assertions::assert_str_eq;
+    use crate::{http::OrderStatus, ApiKeys, Client};
+    use pretty_assertions::{assert_eq, assert_str_eq};
 
     #[ignore]
     #[async_std::test]
@@ -108,7 +163,7 @@ mod tests {
             .open_orders("SHIBUSDT")
             .await
             .unwrap();
-        for bid_ask in orders.asks.iter().chain(orders.bids.iter()) {
+        for bid_ask in orders.all() {
             assert_str_eq!(bid_ask.pair_symbol_normalized, "SHIB_USDT");
         }
     }
@@ -116,6 +171,23 @@ mod tests {
     #[test]
     fn deserialize_open_orders() {
         let json_string = include_str!("sample.json");
-        serde_json::from_str::<OpenOrders>(json_string).unwrap();
+        let orders = serde_json::from_str::<OpenOrders>(json_string).unwrap();
+        assert_eq!(orders.asks[0].status, OrderStatus::Untouched);
+        assert_eq!(orders.asks[1].status, OrderStatus::PartiallyFilled);
+    }
+
+    #[test]
+    fn iterate_both_sides() {
+        let json_string = include_str!("sample.json");
+        let orders = serde_json::from_str::<OpenOrders>(json_string).unwrap();
+        let ids = orders.all().map(|order| order.id).collect::<Vec<_>>();
+        assert_eq!(ids, vec![16_060_235, 16_060_237, 16_071_095, 14_703_840]);
+        assert_eq!(
+            orders.find(16_071_095).map(|order| order.id),
+            Some(16_071_095)
+        );
+        assert_eq!(orders.find(1), None);
+        let owned = orders.clone().into_all().collect::<Vec<_>>();
+        assert_eq!(owned, orders.all().cloned().collect::<Vec<_>>());
     }
 }
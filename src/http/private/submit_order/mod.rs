@@ -1,17 +1,35 @@
 //! Implementation of the submit order endpoint and its helper methods.
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::{stream, StreamExt};
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use surf::http::Method;
 
 use crate::{
-    error::SendRequest,
-    http::{request, OrderMethod, OrderType, Request},
+    error::{Parameter, Response, SendRequest},
+    http::{private::Order, request, Data, OrderMethod, OrderType, Request},
     Client,
 };
 
+/// Parameters of an order to be submitted with [`submit`][Client::submit] or
+/// [`submit_orders`][Client::submit_orders].
+///
+/// # Examples
+/// ```
+/// use btcturk::http::{private::OrderSpec, OrderMethod, OrderType};
+/// use rust_decimal::Decimal;
+///
+/// let spec = OrderSpec::new("BTCUSDT", OrderType::Buy, OrderMethod::Limit)
+///     .price(Decimal::ONE_HUNDRED)
+///     .quantity(Decimal::ONE)
+///     .new_order_client_id("my-order");
+/// ```
+///
+/// See also <https://docs.btcturk.com/private-endpoints/submit-order>.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Parameters {
+pub struct OrderSpec {
     quantity: Option<Decimal>,
     price: Option<Decimal>,
     stop_price: Option<Decimal>,
@@ -21,19 +39,97 @@ struct Parameters {
     pair_symbol: String,
 }
 
-impl<'a, 'i> Client<'i> {
-    async fn submit_order(
+impl OrderSpec {
+    /// Construct an order without quantity, price or stop price.
+    /// # Parameters
+    /// - `pair_symbol`: For example, `BTCUSDT`.
+    /// - `order_type`: Buy or sell.
+    /// - `order_method`: Market, limit, stop market or stop limit.
+    #[must_use]
+    pub fn new(
+        pair_symbol: impl Into<String>,
+        order_type: OrderType,
+        order_method: OrderMethod,
+    ) -> Self {
+        Self {
+            quantity: None,
+            price: None,
+            stop_price: None,
+            new_order_client_id: None,
+            order_method,
+            order_type,
+            pair_symbol: pair_symbol.into(),
+        }
+    }
+
+    /// Set the quantity. Mandatory for market or limit orders.
+    #[must_use]
+    pub const fn quantity(mut self, quantity: Decimal) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    /// Set the price. Ignored for market orders.
+    #[must_use]
+    pub const fn price(mut self, price: Decimal) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// Set the stop price of a stop order.
+    #[must_use]
+    pub const fn stop_price(mut self, stop_price: Decimal) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    /// Set the client identifier of the order. Defaults to the
+    /// [`id`][Client::id] of the client submitting the order.
+    #[must_use]
+    pub fn new_order_client_id(mut self, id: impl Into<String>) -> Self {
+        self.new_order_client_id = Some(id.into());
+        self
+    }
+
+    fn client_id(mut self, id: Option<String>) -> Self {
+        if id.is_some() {
+            self.new_order_client_id = id;
+        }
+        self
+    }
+}
+
+impl Client {
+    /// Submits an order with the given parameters. The helper methods such
+    /// as [`limit_buy`][Client::limit_buy] use this method as well.
+    ///
+    /// If the order has no client identifier, the [`id`][Client::id] of the
+    /// client is used. In the [dry run][crate::http::ClientBuilder::dry_run]
+    /// mode, the order is logged instead of being sent.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    ///
+    /// See also <https://docs.btcturk.com/private-endpoints/submit-order>.
+    pub async fn submit(
         &self,
-        parameters: Parameters,
+        spec: OrderSpec,
     ) -> Result<NewOrder, SendRequest> {
+        if self.is_dry_run() {
+            log::info!("dry run: submit order {spec:?}");
+            return Ok(dry_run_order(self.next_dry_run_id(), spec, self.id()));
+        }
+        let new_order_client_id = spec
+            .new_order_client_id
+            .or_else(|| self.id().map(ToOwned::to_owned));
         let mut params = request::Parameters::new();
-        params.push_decimal("quantity", parameters.quantity);
-        params.push_decimal("price", parameters.price);
-        params.push_decimal("stopPrice", parameters.stop_price);
-        params.push_string("newOrderClientId", parameters.new_order_client_id);
-        params.push_object("orderMethod", Some(parameters.order_method));
-        params.push_object("orderType", Some(parameters.order_type));
-        params.push_string("pairSymbol", Some(parameters.pair_symbol));
+        params.push_decimal("quantity", spec.quantity);
+        params.push_decimal("price", spec.price);
+        params.push_decimal("stopPrice", spec.stop_price);
+        params.push_string("newOrderClientId", new_order_client_id);
+        params.push_object("orderMethod", Some(spec.order_method));
+        params.push_object("orderType", Some(spec.order_type));
+        params.push_string("pairSymbol", Some(spec.pair_symbol));
         self.send(
             Request {
                 endpoint: self.url_cache().submit_cancel_order(),
@@ -46,21 +142,120 @@ impl<'a, 'i> Client<'i> {
         .await
     }
 
+    /// Same as [`submit`][Client::submit] but the order is checked with
+    /// [`Symbol::validate_order`] against the limits of its pair in the
+    /// [`exchange_info_cached`][Client::exchange_info_cached] before the
+    /// request is sent.
+    ///
+    /// [`Symbol::validate_order`]: crate::http::public::exchange_info::Symbol::validate_order
+    /// # Errors
+    /// [`SendRequest`] if the pair is unknown or the order is out of its
+    /// limits, there is an error sending the request or there is an error or
+    /// a malformation in the received response.
+    pub async fn submit_validated(
+        &self,
+        spec: OrderSpec,
+    ) -> Result<NewOrder, SendRequest> {
+        let exchange_info = self.exchange_info_cached().await?;
+        let symbol =
+            exchange_info.symbol(&spec.pair_symbol).ok_or_else(|| {
+                Parameter::new("pair_symbol", spec.pair_symbol.clone())
+            })?;
+        if let Some(quantity) = spec.quantity {
+            symbol.validate_order(spec.price, quantity)?;
+        }
+        self.submit(spec).await
+    }
+
+    /// Submits the orders concurrently and returns a result for each order
+    /// in the same order. An order failing doesn't stop the others from being
+    /// submitted.
+    ///
+    /// The orders are still sent one request per order so consider setting a
+    /// [`rate_limit`][crate::http::ClientBuilder::rate_limit] to avoid
+    /// getting banned.
+    /// # Parameters
+    /// - `orders`: Orders to submit.
+    /// - `concurrency`: Maximum number of orders to be submitted at the same
+    ///   time. Zero is treated as one.
+    pub async fn submit_orders(
+        &self,
+        orders: Vec<OrderSpec>,
+        concurrency: usize,
+    ) -> Vec<Result<NewOrder, SendRequest>> {
+        stream::iter(orders)
+            .map(|order| self.submit(order))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Submits the order tagged with the given client identifier and makes
+    /// sure it is not placed twice when the outcome of the submission is
+    /// ambiguous, e.g. the request timed out or the server failed with a
+    /// status code of 5xx.
+    ///
+    /// After an ambiguous failure, the latest orders of the pair are
+    /// retrieved with [`all_orders`][Client::all_orders] and searched for the
+    /// client identifier. If the order is found, it is returned as placed.
+    /// Otherwise, the order is submitted once more. The identifier should be
+    /// unique to this order for the search to be reliable.
+    ///
+    /// A request that is still in flight on the server may not be listed yet
+    /// so a second order may be placed in rare cases.
+    /// # Parameters
+    /// - `spec`: Order to submit. Its client identifier, if any, is replaced.
+    /// - `client_id`: Client identifier to find the order with.
+    pub async fn submit_idempotent(
+        &self,
+        spec: OrderSpec,
+        client_id: impl Into<String> + Send,
+    ) -> SubmitOutcome {
+        let client_id = client_id.into();
+        let pair_symbol = spec.pair_symbol.clone();
+        let spec = spec.new_order_client_id(client_id.clone());
+        let error = match self.submit(spec.clone()).await {
+            Ok(new_order) => return SubmitOutcome::Placed(new_order),
+            Err(error) if is_ambiguous(&error) => error,
+            Err(error) => return SubmitOutcome::Failed(error),
+        };
+        log::warn!("submission of order `{client_id}` is ambiguous: {error}");
+        let orders =
+            match self.all_orders(None, pair_symbol, .., None, None).await {
+                Ok(orders) => orders,
+                Err(lookup_error) => {
+                    log::warn!(
+                        "failed to look up order `{client_id}`: {lookup_error}"
+                    );
+                    return SubmitOutcome::Unknown(error);
+                }
+            };
+        if let Some(order) = orders
+            .into_iter()
+            .find(|order| order.order_client_id == client_id)
+        {
+            return SubmitOutcome::Placed(order.into());
+        }
+        log::info!("order `{client_id}` is not placed, submitting it again");
+        match self.submit(spec).await {
+            Ok(new_order) => SubmitOutcome::Placed(new_order),
+            Err(error) if is_ambiguous(&error) => SubmitOutcome::Unknown(error),
+            Err(error) => SubmitOutcome::Failed(error),
+        }
+    }
+
     async fn market(
         &self,
         pair_symbol: String,
         quantity: Decimal,
         order_type: OrderType,
+        client_id: Option<String>,
     ) -> Result<NewOrder, SendRequest> {
-        self.submit_order(Parameters {
-            quantity: Some(quantity),
-            price: None,
-            stop_price: None,
-            new_order_client_id: self.id().map(ToOwned::to_owned),
-            order_method: OrderMethod::Market,
-            order_type,
-            pair_symbol,
-        })
+        self.submit(
+            OrderSpec::new(pair_symbol, order_type, OrderMethod::Market)
+                .quantity(quantity)
+                .client_id(client_id),
+        )
         .await
     }
 
@@ -78,10 +273,84 @@ impl<'a, 'i> Client<'i> {
         pair_symbol: impl Into<String> + Send,
         quantity: Decimal,
     ) -> Result<NewOrder, SendRequest> {
-        self.market(pair_symbol.into(), quantity, OrderType::Buy)
+        self.market(pair_symbol.into(), quantity, OrderType::Buy, None)
             .await
     }
 
+    /// Same as [`market_buy`][Client::market_buy] but the order is tagged with
+    /// the given client identifier instead of the [`id`][Client::id] of the
+    /// client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn market_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.market(
+            pair_symbol.into(),
+            quantity,
+            OrderType::Buy,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
+    /// Submits a market buy which spends about `quote_amount` of the quote
+    /// currency, e.g. `100` USDT for `BTCUSDT`, instead of buying a given
+    /// quantity.
+    ///
+    /// The quote amount is converted to a quantity with
+    /// [`OrderBook::estimate_buy_with`] on the current
+    /// [`order_book`][Client::order_book]. The quantity is rounded down with
+    /// [`Symbol::round_quantity`] of the pair in the
+    /// [`exchange_info_cached`][Client::exchange_info_cached]. The book may
+    /// change before the order is matched so the spent amount may differ.
+    ///
+    /// Returns the submitted order and its quantity.
+    ///
+    /// [`OrderBook::estimate_buy_with`]: crate::http::public::OrderBook::estimate_buy_with
+    /// [`Symbol::round_quantity`]: crate::http::public::exchange_info::Symbol::round_quantity
+    /// # Errors
+    /// [`SendRequest`] if the pair is unknown, the amount can't buy any
+    /// quantity, there is an error sending the requests or there is an error
+    /// or a malformation in the received responses.
+    /// # Parameters
+    /// - `pair_symbol`: For example, `BTCUSDT`.
+    /// - `quote_amount`: Amount of the quote currency to spend.
+    pub async fn market_buy_quote(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quote_amount: Decimal,
+    ) -> Result<(NewOrder, Decimal), SendRequest> {
+        let pair_symbol = pair_symbol.into();
+        let exchange_info = self.exchange_info_cached().await?;
+        let symbol = exchange_info.symbol(&pair_symbol).ok_or_else(|| {
+            Parameter::new("pair_symbol", pair_symbol.clone())
+        })?;
+        let estimate = self
+            .order_book(pair_symbol.clone(), None)
+            .await?
+            .estimate_buy_with(quote_amount);
+        let quantity = symbol.round_quantity(estimate.quantity);
+        if quantity <= Decimal::ZERO {
+            return Err(Parameter::new(
+                "quote_amount",
+                quote_amount.to_string(),
+            )
+            .into());
+        }
+        let new_order = self
+            .market(pair_symbol, quantity, OrderType::Buy, None)
+            .await?;
+        Ok((new_order, quantity))
+    }
+
     /// Submits an order with parameters adjusted to perform a market sell.
     ///
     /// [SubmitOrder]: https://docs.btcturk.com/private-endpoints/submit-order
@@ -96,26 +365,48 @@ impl<'a, 'i> Client<'i> {
         pair_symbol: impl Into<String> + Send,
         quantity: Decimal,
     ) -> Result<NewOrder, SendRequest> {
-        self.market(pair_symbol.into(), quantity, OrderType::Sell)
+        self.market(pair_symbol.into(), quantity, OrderType::Sell, None)
             .await
     }
 
+    /// Same as [`market_sell`][Client::market_sell] but the order is tagged
+    /// with the given client identifier instead of the [`id`][Client::id] of
+    /// the client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn market_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.market(
+            pair_symbol.into(),
+            quantity,
+            OrderType::Sell,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
     async fn limit(
         &self,
         pair_symbol: String,
         quantity: Decimal,
         price: Decimal,
         order_type: OrderType,
+        client_id: Option<String>,
     ) -> Result<NewOrder, SendRequest> {
-        self.submit_order(Parameters {
-            quantity: Some(quantity),
-            price: Some(price),
-            stop_price: None,
-            new_order_client_id: self.id().map(ToOwned::to_owned),
-            order_method: OrderMethod::Limit,
-            order_type,
-            pair_symbol,
-        })
+        self.submit(
+            OrderSpec::new(pair_symbol, order_type, OrderMethod::Limit)
+                .price(price)
+                .quantity(quantity)
+                .client_id(client_id),
+        )
         .await
     }
 
@@ -140,10 +431,36 @@ impl<'a, 'i> Client<'i> {
         price: Decimal,
         quantity: Decimal,
     ) -> Result<NewOrder, SendRequest> {
-        self.limit(pair_symbol.into(), quantity, price, OrderType::Buy)
+        self.limit(pair_symbol.into(), quantity, price, OrderType::Buy, None)
             .await
     }
 
+    /// Same as [`limit_buy`][Client::limit_buy] but the order is tagged with
+    /// the given client identifier instead of the [`id`][Client::id] of the
+    /// client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn limit_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.limit(
+            pair_symbol.into(),
+            quantity,
+            price,
+            OrderType::Buy,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
     /// Submits an order with parameters adjusted to perform a limit sell.
     ///
     /// [SubmitOrder]: https://docs.btcturk.com/private-endpoints/submit-order
@@ -165,10 +482,36 @@ impl<'a, 'i> Client<'i> {
         price: Decimal,
         quantity: Decimal,
     ) -> Result<NewOrder, SendRequest> {
-        self.limit(pair_symbol.into(), quantity, price, OrderType::Sell)
+        self.limit(pair_symbol.into(), quantity, price, OrderType::Sell, None)
             .await
     }
 
+    /// Same as [`limit_sell`][Client::limit_sell] but the order is tagged with
+    /// the given client identifier instead of the [`id`][Client::id] of the
+    /// client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn limit_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.limit(
+            pair_symbol.into(),
+            quantity,
+            price,
+            OrderType::Sell,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
     async fn stop_limit(
         &self,
         pair_symbol: String,
@@ -176,16 +519,15 @@ impl<'a, 'i> Client<'i> {
         price: Decimal,
         stop_price: Decimal,
         order_type: OrderType,
+        client_id: Option<String>,
     ) -> Result<NewOrder, SendRequest> {
-        self.submit_order(Parameters {
-            quantity: Some(quantity),
-            price: Some(price),
-            stop_price: Some(stop_price),
-            new_order_client_id: self.id().map(ToOwned::to_owned),
-            order_method: OrderMethod::Limit,
-            order_type,
-            pair_symbol,
-        })
+        self.submit(
+            OrderSpec::new(pair_symbol, order_type, OrderMethod::StopLimit)
+                .price(price)
+                .stop_price(stop_price)
+                .quantity(quantity)
+                .client_id(client_id),
+        )
         .await
     }
 
@@ -219,6 +561,35 @@ impl<'a, 'i> Client<'i> {
             price,
             stop_price,
             OrderType::Buy,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`stop_limit_buy`][Client::stop_limit_buy] but the order is
+    /// tagged with the given client identifier instead of the
+    /// [`id`][Client::id] of the client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn stop_limit_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_limit(
+            pair_symbol.into(),
+            quantity,
+            price,
+            stop_price,
+            OrderType::Buy,
+            Some(client_id.into()),
         )
         .await
     }
@@ -253,6 +624,158 @@ impl<'a, 'i> Client<'i> {
             price,
             stop_price,
             OrderType::Sell,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`stop_limit_sell`][Client::stop_limit_sell] but the order is
+    /// tagged with the given client identifier instead of the
+    /// [`id`][Client::id] of the client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn stop_limit_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        price: Decimal,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_limit(
+            pair_symbol.into(),
+            quantity,
+            price,
+            stop_price,
+            OrderType::Sell,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
+    async fn stop_market(
+        &self,
+        pair_symbol: String,
+        quantity: Decimal,
+        stop_price: Decimal,
+        order_type: OrderType,
+        client_id: Option<String>,
+    ) -> Result<NewOrder, SendRequest> {
+        self.submit(
+            OrderSpec::new(pair_symbol, order_type, OrderMethod::StopMarket)
+                .stop_price(stop_price)
+                .quantity(quantity)
+                .client_id(client_id),
+        )
+        .await
+    }
+
+    /// Submits an order with parameters adjusted to perform a stop market
+    /// buy.
+    ///
+    /// [SubmitOrder]: https://docs.btcturk.com/private-endpoints/submit-order
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `pair_symbol`: For example, `BTCUSDT`.
+    /// - `stop_price`: For stop orders.
+    /// - `quantity`: Mandatory for market or limit orders.
+    pub async fn stop_market_buy(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_market(
+            pair_symbol.into(),
+            quantity,
+            stop_price,
+            OrderType::Buy,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`stop_market_buy`][Client::stop_market_buy] but the order is
+    /// tagged with the given client identifier instead of the
+    /// [`id`][Client::id] of the client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn stop_market_buy_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_market(
+            pair_symbol.into(),
+            quantity,
+            stop_price,
+            OrderType::Buy,
+            Some(client_id.into()),
+        )
+        .await
+    }
+
+    /// Submits an order with parameters adjusted to perform a stop market
+    /// sell.
+    ///
+    /// [SubmitOrder]: https://docs.btcturk.com/private-endpoints/submit-order
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `pair_symbol`: For example, `BTCUSDT`.
+    /// - `stop_price`: For stop orders.
+    /// - `quantity`: Mandatory for market or limit orders.
+    pub async fn stop_market_sell(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_market(
+            pair_symbol.into(),
+            quantity,
+            stop_price,
+            OrderType::Sell,
+            None,
+        )
+        .await
+    }
+
+    /// Same as [`stop_market_sell`][Client::stop_market_sell] but the order is
+    /// tagged with the given client identifier instead of the
+    /// [`id`][Client::id] of the client.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `client_id`: Client identifier of this order only. Use it to match
+    ///   the order with the transactions and order updates.
+    pub async fn stop_market_sell_with_id(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        stop_price: Decimal,
+        quantity: Decimal,
+        client_id: impl Into<String> + Send,
+    ) -> Result<NewOrder, SendRequest> {
+        self.stop_market(
+            pair_symbol.into(),
+            quantity,
+            stop_price,
+            OrderType::Sell,
+            Some(client_id.into()),
         )
         .await
     }
@@ -265,6 +788,7 @@ impl<'a, 'i> Client<'i> {
 /// See also <https://docs.btcturk.com/private-endpoints/submit-order>
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct NewOrder {
     #[allow(missing_docs)]
     pub id: i64,
@@ -285,21 +809,118 @@ pub struct NewOrder {
     pub pair_symbol: String,
     #[allow(missing_docs)]
     pub pair_symbol_normalized: String,
-    #[allow(missing_docs)]
-    pub new_order_client_id: String,
+    /// `None` if the order was submitted without a client identifier.
+    #[serde(deserialize_with = "crate::http::empty_as_none")]
+    pub new_order_client_id: Option<String>,
+}
+
+impl Data for NewOrder {}
+
+/// Build the order returned instead of submitting it in the dry run mode.
+fn dry_run_order(
+    id: i64,
+    spec: OrderSpec,
+    client_id: Option<&str>,
+) -> NewOrder {
+    let date_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        });
+    NewOrder {
+        id,
+        date_time,
+        r#type: spec.order_type,
+        method: spec.order_method,
+        price: spec.price,
+        stop_price: spec.stop_price,
+        quantity: spec.quantity,
+        pair_symbol_normalized: spec.pair_symbol.clone(),
+        pair_symbol: spec.pair_symbol,
+        new_order_client_id: spec
+            .new_order_client_id
+            .or_else(|| client_id.map(ToOwned::to_owned)),
+    }
+}
+
+/// Order found after an ambiguous submission. The stop price is not listed
+/// so it is `None`.
+impl From<Order> for NewOrder {
+    fn from(order: Order) -> Self {
+        Self {
+            id: order.id,
+            date_time: order.time,
+            r#type: order.r#type,
+            method: order.method,
+            price: Some(order.price),
+            stop_price: None,
+            quantity: Some(order.quantity),
+            pair_symbol: order.pair_symbol,
+            pair_symbol_normalized: order.pair_symbol_normalized,
+            new_order_client_id: Some(order.order_client_id)
+                .filter(|id| !id.is_empty()),
+        }
+    }
+}
+
+/// Result of [`submit_idempotent`][Client::submit_idempotent].
+#[derive(Debug)]
+pub enum SubmitOutcome {
+    /// Order is placed, either by the first submission or an earlier one
+    /// found after an ambiguous failure.
+    Placed(NewOrder),
+    /// It couldn't be determined whether the order is placed. Check the
+    /// orders before submitting it again.
+    Unknown(SendRequest),
+    /// Order is not placed.
+    Failed(SendRequest),
+}
+
+/// Whether the order may have been placed despite the error.
+fn is_ambiguous(error: &SendRequest) -> bool {
+    match error {
+        SendRequest::Timeout { .. }
+        | SendRequest::SurfError { .. }
+        | SendRequest::SerdeJsonError { .. }
+        | SendRequest::DeserializeError { .. } => true,
+        SendRequest::BadStatusCode { status_code, .. } => {
+            status_code.is_server_error()
+        }
+        SendRequest::ResponseError { source } => {
+            !matches!(source, Response::Unsuccessful { .. })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl NewOrder {
+    /// Get [`date_time`][Self::date_time] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.date_time)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use crate::{ApiKeys, Client};
+    use crate::{
+        error::SendRequest,
+        http::{
+            mock::{self, MockHttpClient},
+            OrderMethod, OrderType,
+        },
+        ApiKeys, Client,
+    };
     use pretty_assertions::{assert_eq, assert_str_eq};
     use rust_decimal::Decimal;
+    use surf::StatusCode;
 
-    use super::NewOrder;
+    use super::{NewOrder, OrderSpec, SubmitOutcome};
 
-    fn init_client() -> Client<'static> {
+    fn init_client() -> Client {
         let _ = env_logger::builder().is_test(true).try_init();
         let keys = ApiKeys::load_from_env_var();
         Client::new(Some(keys), Some("test")).unwrap()
@@ -312,7 +933,7 @@ mod tests {
             .market_buy("XRPUSDT", Decimal::TEN)
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "XRP_USDT");
         assert_eq!(new_order.price, None);
         assert_eq!(new_order.quantity, Some(Decimal::TEN));
@@ -325,7 +946,7 @@ mod tests {
             .market_sell("DOGEUSDT", Decimal::ONE_HUNDRED)
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "DOGE_USDT");
         assert_eq!(new_order.price, None);
         assert_eq!(new_order.quantity, Some(Decimal::ONE_HUNDRED));
@@ -340,7 +961,7 @@ mod tests {
             .limit_buy("XRPUSDT", price, quantity)
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "XRP_USDT");
         assert_eq!(new_order.price, Some(price));
         assert_eq!(new_order.quantity, Some(quantity));
@@ -353,7 +974,7 @@ mod tests {
             .limit_buy("ADAUSDT", Decimal::ONE, Decimal::TEN)
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "ADA_USDT");
         assert_eq!(new_order.price, Some(Decimal::TEN));
         assert_eq!(new_order.quantity, Some(Decimal::ONE));
@@ -371,7 +992,7 @@ mod tests {
             )
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "DOGE_USDT");
         assert_eq!(new_order.price, Some(Decimal::ONE_HUNDRED));
         assert_eq!(new_order.stop_price, Some(Decimal::TEN));
@@ -385,16 +1006,293 @@ mod tests {
             .stop_limit_buy("XRPUSDT", Decimal::ONE, Decimal::TWO, Decimal::TEN)
             .await
             .unwrap();
-        assert_str_eq!(new_order.new_order_client_id, "test");
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
         assert_str_eq!(new_order.pair_symbol_normalized, "XRP_USDT");
         assert_eq!(new_order.price, Some(Decimal::ONE));
         assert_eq!(new_order.stop_price, Some(Decimal::TWO));
         assert_eq!(new_order.quantity, Some(Decimal::TEN));
     }
 
+    #[ignore]
+    #[async_std::test]
+    async fn stop_market_buy() {
+        let new_order = init_client()
+            .stop_market_buy("DOGEUSDT", Decimal::TEN, Decimal::ONE_HUNDRED)
+            .await
+            .unwrap();
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
+        assert_str_eq!(new_order.pair_symbol_normalized, "DOGE_USDT");
+        assert_eq!(new_order.price, None);
+        assert_eq!(new_order.stop_price, Some(Decimal::TEN));
+        assert_eq!(new_order.quantity, Some(Decimal::ONE_HUNDRED));
+    }
+
+    #[ignore]
+    #[async_std::test]
+    async fn stop_market_sell() {
+        let new_order = init_client()
+            .stop_market_sell("XRPUSDT", Decimal::ONE, Decimal::TEN)
+            .await
+            .unwrap();
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
+        assert_str_eq!(new_order.pair_symbol_normalized, "XRP_USDT");
+        assert_eq!(new_order.price, None);
+        assert_eq!(new_order.stop_price, Some(Decimal::ONE));
+        assert_eq!(new_order.quantity, Some(Decimal::TEN));
+    }
+
+    #[async_std::test]
+    async fn submit_orders() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let backend = MockHttpClient::new();
+        backend
+            .push(StatusCode::BadRequest, "")
+            .push_data(include_str!("sample.json"));
+        let client = Client::with_http_client(
+            backend.client(),
+            Some(mock::keys()),
+            Some("test"),
+        );
+        let order = |price| {
+            OrderSpec::new("BTCTRY", OrderType::Buy, OrderMethod::Limit)
+                .price(price)
+                .quantity(Decimal::ONE)
+        };
+        let results = client
+            .submit_orders(
+                vec![
+                    order(Decimal::ONE),
+                    order(Decimal::TWO).new_order_client_id("second"),
+                ],
+                1,
+            )
+            .await;
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok());
+
+        let requests = backend.requests();
+        let body = |index: usize| {
+            serde_json::from_str::<serde_json::Value>(&requests[index].body)
+                .unwrap()
+        };
+        assert_eq!(body(0)["newOrderClientId"], "test");
+        assert_eq!(body(1)["newOrderClientId"], "second");
+        assert_eq!(body(1)["orderMethod"], "limit");
+    }
+
+    #[async_std::test]
+    async fn limit_buy_with_id() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let backend = MockHttpClient::new();
+        backend
+            .push_data(include_str!("sample.json"))
+            .push_data(include_str!("sample.json"));
+        let client = Client::with_http_client(
+            backend.client(),
+            Some(mock::keys()),
+            Some("test"),
+        );
+        client
+            .limit_buy_with_id("BTCTRY", Decimal::ONE, Decimal::TEN, "signal")
+            .await
+            .unwrap();
+        client
+            .limit_buy("BTCTRY", Decimal::ONE, Decimal::TEN)
+            .await
+            .unwrap();
+
+        let requests = backend.requests();
+        let body = |index: usize| {
+            serde_json::from_str::<serde_json::Value>(&requests[index].body)
+                .unwrap()
+        };
+        assert_eq!(body(0)["newOrderClientId"], "signal");
+        assert_eq!(body(1)["newOrderClientId"], "test");
+    }
+
+    #[async_std::test]
+    async fn submit_stop_market() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let backend = MockHttpClient::new();
+        backend.push_data(include_str!("sample.json"));
+        let client = backend.client_with_keys();
+        client
+            .submit(
+                OrderSpec::new(
+                    "BTCTRY",
+                    OrderType::Sell,
+                    OrderMethod::StopMarket,
+                )
+                .stop_price(Decimal::TEN)
+                .quantity(Decimal::ONE),
+            )
+            .await
+            .unwrap();
+
+        let body = serde_json::from_str::<serde_json::Value>(
+            &backend.requests()[0].body,
+        )
+        .unwrap();
+        assert_eq!(body["orderType"], "sell");
+        assert_eq!(body["stopPrice"], "10");
+        assert_eq!(body.get("price"), None);
+        assert_eq!(body.get("newOrderClientId"), None);
+    }
+
+    #[async_std::test]
+    async fn submit_stop_limit() {
+        let backend = MockHttpClient::new();
+        backend.push_data(include_str!("sample.json"));
+        let client = backend.client_with_keys();
+        client
+            .stop_limit_buy("BTCTRY", Decimal::TEN, Decimal::TWO, Decimal::ONE)
+            .await
+            .unwrap();
+
+        let body = serde_json::from_str::<serde_json::Value>(
+            &backend.requests()[0].body,
+        )
+        .unwrap();
+        assert_eq!(body["orderMethod"], "stoplimit");
+        assert_eq!(body["orderType"], "buy");
+        assert_eq!(body["price"], "10");
+        assert_eq!(body["stopPrice"], "2");
+    }
+
+    #[async_std::test]
+    async fn reject_low_exchange_value() {
+        let backend = MockHttpClient::new();
+        backend
+            .push_data(include_str!("../../public/exchange_info/sample.json"));
+        let client = backend.client_with_keys();
+        let error = client
+            .submit_validated(
+                OrderSpec::new("BTCTRY", OrderType::Buy, OrderMethod::Limit)
+                    .price(Decimal::from(500_000))
+                    .quantity(Decimal::from_str("0.0001").unwrap()),
+            )
+            .await
+            .unwrap_err();
+        let SendRequest::ParameterError { source } = error else {
+            panic!("unexpected error: `{error}`");
+        };
+        assert_str_eq!(source.name(), "exchange_value");
+        assert_eq!(backend.requests().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn submit_idempotent() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        let spec = OrderSpec::new("BTCTRY", OrderType::Buy, OrderMethod::Limit)
+            .price(Decimal::ONE)
+            .quantity(Decimal::ONE);
+
+        backend
+            .push(StatusCode::InternalServerError, "")
+            .push_data(include_str!("../all_orders/sample.json"));
+        let outcome = client.submit_idempotent(spec.clone(), "test").await;
+        let SubmitOutcome::Placed(new_order) = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(new_order.id, 9_932_534);
+        assert_eq!(backend.requests().len(), 2);
+
+        backend
+            .push(StatusCode::InternalServerError, "")
+            .push_data("[]")
+            .push_data(include_str!("sample.json"));
+        let outcome = client.submit_idempotent(spec.clone(), "test").await;
+        assert!(matches!(outcome, SubmitOutcome::Placed(_)));
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 5);
+        let body = serde_json::from_str::<serde_json::Value>(&requests[4].body)
+            .unwrap();
+        assert_eq!(body["newOrderClientId"], "test");
+
+        backend.push(StatusCode::InternalServerError, "");
+        let outcome = client.submit_idempotent(spec.clone(), "test").await;
+        assert!(matches!(outcome, SubmitOutcome::Unknown(_)));
+
+        backend.push(StatusCode::BadRequest, "");
+        let outcome = client.submit_idempotent(spec, "test").await;
+        assert!(matches!(outcome, SubmitOutcome::Failed(_)));
+        assert_eq!(backend.requests().len(), 8);
+    }
+
+    #[async_std::test]
+    async fn market_buy_with_quote_amount() {
+        let backend = MockHttpClient::new();
+        backend
+            .push_data(include_str!("../../public/exchange_info/sample.json"))
+            .push_data(include_str!("../../public/order_book/sample.json"))
+            .push_data(include_str!("sample.json"));
+        let client = backend.client_with_keys();
+        let (_, quantity) = client
+            .market_buy_quote("BTCTRY", Decimal::ONE_HUNDRED)
+            .await
+            .unwrap();
+        assert_eq!(quantity, Decimal::from_str("0.00274717").unwrap());
+
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 3);
+        let body = serde_json::from_str::<serde_json::Value>(&requests[2].body)
+            .unwrap();
+        assert_eq!(body["quantity"], "0.00274717");
+        assert_eq!(body["orderMethod"], "market");
+
+        backend.push_data(include_str!("../../public/order_book/sample.json"));
+        let error = client
+            .market_buy_quote("BTCTRY", Decimal::from_str("0.0001").unwrap())
+            .await
+            .unwrap_err();
+        let SendRequest::ParameterError { source } = error else {
+            panic!("unexpected error: `{error}`");
+        };
+        assert_str_eq!(source.name(), "quote_amount");
+    }
+
+    #[async_std::test]
+    async fn dry_run_orders() {
+        let backend = MockHttpClient::new();
+        let client = Client::builder()
+            .keys(mock::keys())
+            .id("test")
+            .http_client(backend.client())
+            .dry_run(true)
+            .build()
+            .unwrap();
+        assert!(client.is_dry_run());
+        let first = client
+            .limit_buy("BTCTRY", Decimal::ONE, Decimal::TEN)
+            .await
+            .unwrap();
+        let second = client.market_sell("BTCTRY", Decimal::ONE).await.unwrap();
+        assert_eq!(first.id, -1);
+        assert_eq!(second.id, -2);
+        assert_eq!(first.price, Some(Decimal::ONE));
+        assert_eq!(first.quantity, Some(Decimal::TEN));
+        assert_eq!(first.new_order_client_id.as_deref(), Some("test"));
+        assert_eq!(second.r#type, OrderType::Sell);
+        let result = client.cancel_order(first.id).await.unwrap();
+        assert_eq!(result.id, first.id);
+        assert!(backend.requests().is_empty());
+    }
+
     #[test]
     fn deserialize_new_order() {
         let json_string = include_str!("sample.json");
-        serde_json::from_str::<NewOrder>(json_string).unwrap();
+        let new_order = serde_json::from_str::<NewOrder>(json_string).unwrap();
+        assert_eq!(new_order.new_order_client_id.as_deref(), Some("test"));
+
+        let json_string = json_string.replace(
+            r#""newOrderClientId": "test""#,
+            r#""newOrderClientId": """#,
+        );
+        let new_order = serde_json::from_str::<NewOrder>(&json_string).unwrap();
+        assert_eq!(new_order.new_order_client_id, None);
     }
 }
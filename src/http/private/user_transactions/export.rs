@@ -0,0 +1,261 @@
+//! Export of the trade transactions to CSV.
+
+use std::{collections::HashSet, io::Write, ops::Range};
+
+use crate::{error::SendRequest, Client};
+
+use super::TradeTransaction;
+
+/// Longest date range, in milliseconds, the transactions are requested for
+/// at once. It is the 30 days the API defaults to.
+pub const TRANSACTION_WINDOW: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Columns of the CSV written by
+/// [`export_trade_transactions_csv`][Client::export_trade_transactions_csv].
+const HEADER: [&str; 10] = [
+    "timestamp",
+    "id",
+    "order_id",
+    "numerator_symbol",
+    "denominator_symbol",
+    "side",
+    "price",
+    "amount",
+    "fee",
+    "tax",
+];
+
+impl Client {
+    /// Get the trade transactions in the date range and write them as CSV,
+    /// oldest first, with a header row.
+    ///
+    /// The range is split into windows of [`TRANSACTION_WINDOW`] which are
+    /// requested one after the other. The endpoint may cut a long list at a
+    /// limit and it can't be paged other than by the date range. So the
+    /// ranges before the oldest and after the latest transaction of each
+    /// received batch are requested as well, until a request brings no new
+    /// transaction. This takes at least three requests per window which has
+    /// any transactions. A transaction returned more than once is written
+    /// once.
+    ///
+    /// Each window is written as soon as it is received, so the windows
+    /// preceding a failed request are written already.
+    ///
+    /// Returns the number of the written transactions.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending a request, there is an
+    /// error or a malformation in a received response or the CSV couldn't be
+    /// written.
+    /// # Parameters
+    /// - `writer`: Destination of the CSV, e.g. a file.
+    /// - `date_range`: Start-end date timestamp range in milliseconds.
+    pub async fn export_trade_transactions_csv(
+        &self,
+        writer: impl Write + Send,
+        date_range: Range<u64>,
+    ) -> Result<usize, SendRequest> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(HEADER)?;
+        let mut ids = HashSet::new();
+        let mut count = 0;
+        for window in windows(date_range) {
+            let mut transactions =
+                self.window_transactions(window, &mut ids).await?;
+            transactions.sort_by_key(|transaction| {
+                (transaction.timestamp, transaction.id)
+            });
+            for transaction in &transactions {
+                write_record(&mut writer, transaction)?;
+            }
+            writer.flush().map_err(csv::Error::from)?;
+            count += transactions.len();
+        }
+        Ok(count)
+    }
+
+    /// Get the transactions in the window whose identifiers are not in `ids`
+    /// and add their identifiers. See
+    /// [`export_trade_transactions_csv`][Self::export_trade_transactions_csv]
+    /// for the paging.
+    async fn window_transactions(
+        &self,
+        window: Range<u64>,
+        ids: &mut HashSet<i64>,
+    ) -> Result<Vec<TradeTransaction>, SendRequest> {
+        let mut transactions = Vec::new();
+        let mut ranges = vec![window];
+        while let Some(range) = ranges.pop() {
+            let batch = self
+                .trade_transactions(
+                    None,
+                    None,
+                    Vec::<String>::new(),
+                    range.clone(),
+                )
+                .await?;
+            let new = batch
+                .into_iter()
+                .filter(|transaction| ids.insert(transaction.id))
+                .collect::<Vec<_>>();
+            let timestamps =
+                new.iter().map(|transaction| transaction.timestamp);
+            if let (Some(oldest), Some(latest)) =
+                (timestamps.clone().min(), timestamps.max())
+            {
+                // The ranges include the bounds since more transactions may
+                // share the timestamp of the oldest or the latest one.
+                let clamp =
+                    |timestamp: u64| timestamp.clamp(range.start, range.end);
+                ranges.push(range.start..clamp(oldest.saturating_add(1)));
+                ranges.push(clamp(latest)..range.end);
+            }
+            transactions.extend(new);
+        }
+        Ok(transactions)
+    }
+}
+
+/// Split the range into consecutive ranges of at most
+/// [`TRANSACTION_WINDOW`]. An empty range yields itself.
+fn windows(range: Range<u64>) -> Vec<Range<u64>> {
+    if range.is_empty() {
+        return vec![range];
+    }
+    let mut windows = Vec::new();
+    let mut start = range.start;
+    while start < range.end {
+        let end = start.saturating_add(TRANSACTION_WINDOW).min(range.end);
+        windows.push(start..end);
+        start = end;
+    }
+    windows
+}
+
+fn write_record(
+    writer: &mut csv::Writer<impl Write>,
+    transaction: &TradeTransaction,
+) -> Result<(), csv::Error> {
+    writer.write_record([
+        transaction.timestamp.to_string().as_str(),
+        &transaction.id.to_string(),
+        &transaction.order_id.to_string(),
+        &transaction.numerator_symbol,
+        &transaction.denominator_symbol,
+        &transaction.order_type.to_string(),
+        &transaction.price.to_string(),
+        &transaction.amount.to_string(),
+        &transaction.fee.to_string(),
+        &transaction.tax.to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use surf::StatusCode;
+
+    use super::{windows, TRANSACTION_WINDOW};
+    use crate::http::mock::MockHttpClient;
+
+    #[test]
+    fn split_into_windows() {
+        assert_eq!(windows(5..5), vec![5..5]);
+        assert_eq!(windows(0..10), vec![0..10]);
+        let end = TRANSACTION_WINDOW * 2 + 1;
+        assert_eq!(
+            windows(0..end),
+            vec![
+                0..TRANSACTION_WINDOW,
+                TRANSACTION_WINDOW..TRANSACTION_WINDOW * 2,
+                TRANSACTION_WINDOW * 2..end,
+            ]
+        );
+    }
+
+    /// Transactions of the sample with the given identifiers and
+    /// timestamps.
+    fn transactions(transactions: &[(i64, u64)]) -> String {
+        let sample = include_str!("trade_sample.json");
+        let sample =
+            sample.trim().trim_start_matches('[').trim_end_matches(']');
+        let transactions = transactions
+            .iter()
+            .map(|(id, timestamp)| {
+                sample
+                    .replace("552887776", &id.to_string())
+                    .replace("1635329637237", &timestamp.to_string())
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", transactions.join(","))
+    }
+
+    fn row(id: i64, timestamp: u64) -> String {
+        format!(
+            "{timestamp},{id},5111697972,XLM,TRY,buy,3.267,146.6727,\
+            -0.73095036,-0.13157106\n"
+        )
+    }
+
+    const HEADER: &str = "timestamp,id,order_id,numerator_symbol,\
+        denominator_symbol,side,price,amount,fee,tax\n";
+
+    #[async_std::test]
+    async fn export_trade_transactions_csv() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        backend
+            // The first window is cut to its latest transaction.
+            .push_data(&transactions(&[(2, 2000)]))
+            .push_data(&transactions(&[(2, 2000)]))
+            .push_data(&transactions(&[(1, 1000), (2, 2000)]))
+            .push_data(&transactions(&[(1, 1000), (2, 2000)]))
+            .push_data(&transactions(&[(1, 1000)]))
+            .push_data("[]");
+        let mut csv = Vec::new();
+        let count = client
+            .export_trade_transactions_csv(&mut csv, 0..TRANSACTION_WINDOW + 1)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_str_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!("{HEADER}{}{}", row(1, 1000), row(2, 2000))
+        );
+        let queries = backend
+            .requests()
+            .iter()
+            .map(|request| request.url.query().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            queries,
+            [
+                "endDate=2592000000&startDate=0",
+                "endDate=2592000000&startDate=2000",
+                "endDate=2001&startDate=0",
+                "endDate=2001&startDate=1000",
+                "endDate=1001&startDate=0",
+                "endDate=2592000001&startDate=2592000000",
+            ]
+        );
+    }
+
+    #[async_std::test]
+    async fn write_windows_before_failure() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        backend
+            .push_data(&transactions(&[(1, 1000)]))
+            .push_data("[]")
+            .push_data("[]")
+            .push(StatusCode::InternalServerError, "");
+        let mut csv = Vec::new();
+        client
+            .export_trade_transactions_csv(&mut csv, 0..TRANSACTION_WINDOW + 1)
+            .await
+            .unwrap_err();
+        assert_str_eq!(
+            String::from_utf8(csv).unwrap(),
+            format!("{HEADER}{}", row(1, 1000))
+        );
+    }
+}
@@ -2,15 +2,20 @@
 
 use rust_decimal::Decimal;
 use serde::Deserialize;
-use std::{fmt::Display, ops::Range};
+use std::{collections::HashMap, fmt::Display, ops::RangeBounds};
 use surf::http::Method;
 
 use crate::{
     error::{self, SendRequest},
-    http::{request::Parameters, OrderType, Request},
+    http::{request::Parameters, Data, OrderType, Request},
     Client,
 };
 
+#[cfg(feature = "csv")]
+mod export;
+#[cfg(feature = "csv")]
+pub use export::TRANSACTION_WINDOW;
+
 #[allow(missing_docs)]
 #[derive(
     Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
@@ -50,7 +55,7 @@ impl TryFrom<String> for TransactionType {
     }
 }
 
-impl Client<'_> {
+impl Client {
     /// Get all user trade transactions.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
@@ -58,10 +63,14 @@ impl Client<'_> {
     /// # Parameters
     /// - `order_id`: If you use this parameter, you can't use the other
     /// parameters (they will be ignored).
-    /// - `type`: Type of the trade (`buy` or `sell`). Defaults to both.
-    /// - `symbols`: Array of `btc`, `try`, etc. Can be empty.
-    /// - `date_range`: Start-end date timestamp range. Defaults to last 30
-    /// days.
+    /// - `type`: Type of the trade (`buy` or `sell`). Defaults to both. Only
+    ///   a single type is taken, unlike the `symbols`, since filtering by
+    ///   both types is the same as not filtering.
+    /// - `symbols`: Array of `btc`, `try`, etc. Can be empty. Each symbol is
+    ///   sent as a separate `symbol` parameter.
+    /// - `date_range`: Start-end date timestamp range. An unbounded side is
+    ///   left out. Defaults to last 30 days if both sides are unbounded, e.g.
+    ///   `..`.
     ///
     /// See also <https://docs.btcturk.com/private-endpoints/user-transactions>.
     pub async fn trade_transactions(
@@ -69,20 +78,20 @@ impl Client<'_> {
         order_id: Option<i64>,
         r#type: Option<OrderType>,
         symbols: Vec<impl Into<String> + Send>,
-        date_range: Option<Range<u64>>,
+        date_range: impl RangeBounds<u64> + Send,
     ) -> Result<Vec<TradeTransaction>, SendRequest> {
         let mut parameters = Parameters::new();
         if let Some(id) = order_id {
             parameters.push_number("orderId", Some(id));
         } else {
             parameters.push_object("type", r#type);
-            for symbol in symbols {
-                parameters.push_string("symbol", Some(symbol.into()));
-            }
-            if let Some(range) = date_range {
-                parameters.push_number("startDate", Some(range.start));
-                parameters.push_number("endDate", Some(range.end));
-            }
+            parameters
+                .push_strings("symbol", symbols.into_iter().map(Into::into));
+            parameters.push_range(
+                "date_range",
+                ("startDate", "endDate"),
+                date_range,
+            )?;
         }
         self.send(
             Request {
@@ -100,22 +109,20 @@ impl Client<'_> {
         &self,
         r#type: Option<TransactionType>,
         symbols: Vec<impl Into<String> + Send>,
-        date_range: Option<Range<u64>>,
+        date_range: impl RangeBounds<u64> + Send,
         fiat: bool,
     ) -> Result<T, SendRequest>
     where
-        for<'de> T: Deserialize<'de>,
+        for<'de> T: Data + Deserialize<'de>,
     {
         let mut parameters = Parameters::new();
         parameters.push_object("type", r#type);
-
-        for symbol in symbols {
-            parameters.push_string("symbol", Some(symbol.into()));
-        }
-        if let Some(range) = date_range {
-            parameters.push_number("startDate", Some(range.start));
-            parameters.push_number("endDate", Some(range.end));
-        }
+        parameters.push_strings("symbol", symbols.into_iter().map(Into::into));
+        parameters.push_range(
+            "date_range",
+            ("startDate", "endDate"),
+            date_range,
+        )?;
         let endpoint = if fiat {
             self.url_cache().fiat_transactions()
         } else {
@@ -141,15 +148,16 @@ impl Client<'_> {
     /// - `type`: Type of the transaction (`deposit` or `withdrawal`). Defaults
     /// to both.
     /// - `symbols`: Array of `btc`, `eth`, etc. Can be empty.
-    /// - `date_range`: Start-end date timestamp range. Defaults to last 30
-    /// days.
+    /// - `date_range`: Start-end date timestamp range. An unbounded side is
+    ///   left out. Defaults to last 30 days if both sides are unbounded, e.g.
+    ///   `..`.
     ///
     /// See also <https://docs.btcturk.com/private-endpoints/user-transactions>.
     pub async fn crypto_transactions(
         &self,
         r#type: Option<TransactionType>,
         symbols: Vec<impl Into<String> + Send>,
-        date_range: Option<Range<u64>>,
+        date_range: impl RangeBounds<u64> + Send,
     ) -> Result<Vec<CryptoTransaction>, SendRequest> {
         self.normal_transactions(r#type, symbols, date_range, false)
             .await
@@ -163,15 +171,16 @@ impl Client<'_> {
     /// - `type`: Type of the transaction (`deposit` or `withdrawal`). Defaults
     /// to both.
     /// - `symbols`: Array of `try`, etc. Can be empty.
-    /// - `date_range`: Start-end date timestamp range. Defaults to last 30
-    /// days.
+    /// - `date_range`: Start-end date timestamp range. An unbounded side is
+    ///   left out. Defaults to last 30 days if both sides are unbounded, e.g.
+    ///   `..`.
     ///
     /// See also <https://docs.btcturk.com/private-endpoints/user-transactions>.
     pub async fn fiat_transactions(
         &self,
         r#type: Option<TransactionType>,
         symbols: Vec<impl Into<String> + Send>,
-        date_range: Option<Range<u64>>,
+        date_range: impl RangeBounds<u64> + Send,
     ) -> Result<Vec<FiatTransaction>, SendRequest> {
         self.normal_transactions(r#type, symbols, date_range, true)
             .await
@@ -187,6 +196,7 @@ impl Client<'_> {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct TradeTransaction {
     #[allow(missing_docs)]
     pub price: Decimal,
@@ -210,6 +220,15 @@ pub struct TradeTransaction {
     pub tax: Decimal,
 }
 
+#[cfg(feature = "chrono")]
+impl TradeTransaction {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
 /// **Sample**:
 /// ```json
 #[doc = include_str!("crypto_sample.json")]
@@ -219,6 +238,7 @@ pub struct TradeTransaction {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CryptoTransaction {
     #[allow(missing_docs)]
     pub balance_type: TransactionType,
@@ -246,6 +266,15 @@ pub struct CryptoTransaction {
     pub tax: Decimal,
 }
 
+#[cfg(feature = "chrono")]
+impl CryptoTransaction {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
 /// **Sample**:
 /// ```json
 #[doc = include_str!("fiat_sample.json")]
@@ -255,6 +284,7 @@ pub struct CryptoTransaction {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FiatTransaction {
     #[allow(missing_docs)]
     pub balance_type: TransactionType,
@@ -263,6 +293,14 @@ pub struct FiatTransaction {
     #[allow(missing_docs)]
     pub address: Option<String>,
     #[allow(missing_docs)]
+    pub tag: Option<String>,
+    #[allow(missing_docs)]
+    pub tx_hash: Option<String>,
+    #[allow(missing_docs)]
+    pub confirmation_count: Option<u64>,
+    #[allow(missing_docs)]
+    pub is_confirmed: bool,
+    #[allow(missing_docs)]
     pub id: i64,
     #[allow(missing_docs)]
     pub timestamp: u64,
@@ -274,12 +312,136 @@ pub struct FiatTransaction {
     pub tax: Decimal,
 }
 
+#[cfg(feature = "chrono")]
+impl FiatTransaction {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
+/// Transaction with a fee and a tax. See [`total_fees`] and
+/// [`fees_by_symbol`].
+pub trait Charged {
+    /// Fee as sent by the API. Charged fees are negative.
+    fn fee(&self) -> Decimal;
+    /// Tax as sent by the API. Charged taxes are negative.
+    fn tax(&self) -> Decimal;
+    /// Currency the fee and the tax are in. The denominator for a trade,
+    /// e.g. `TRY` for `BTCTRY`, and the currency of the transaction
+    /// otherwise.
+    fn charge_symbol(&self) -> &str;
+}
+
+impl Charged for TradeTransaction {
+    fn fee(&self) -> Decimal {
+        self.fee
+    }
+
+    fn tax(&self) -> Decimal {
+        self.tax
+    }
+
+    fn charge_symbol(&self) -> &str {
+        &self.denominator_symbol
+    }
+}
+
+impl Charged for CryptoTransaction {
+    fn fee(&self) -> Decimal {
+        self.fee
+    }
+
+    fn tax(&self) -> Decimal {
+        self.tax
+    }
+
+    fn charge_symbol(&self) -> &str {
+        &self.currency_symbol
+    }
+}
+
+impl Charged for FiatTransaction {
+    fn fee(&self) -> Decimal {
+        self.fee
+    }
+
+    fn tax(&self) -> Decimal {
+        self.tax
+    }
+
+    fn charge_symbol(&self) -> &str {
+        &self.currency_symbol
+    }
+}
+
+/// Fees and taxes paid over some transactions. The amounts are positive
+/// when paid, i.e. the signs of the values sent by the API are flipped. A
+/// refund reduces them.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FeeTotal {
+    /// Sum of the fees paid.
+    pub fee: Decimal,
+    /// Sum of the taxes paid.
+    pub tax: Decimal,
+}
+
+impl FeeTotal {
+    /// Get the sum of the fees and the taxes paid.
+    #[must_use]
+    pub fn total(&self) -> Decimal {
+        self.fee + self.tax
+    }
+
+    fn add(&mut self, transaction: &impl Charged) {
+        self.fee -= transaction.fee();
+        self.tax -= transaction.tax();
+    }
+}
+
+/// Sum the fees and the taxes paid over the transactions. Only add up the
+/// transactions which are charged in the same currency, e.g. with
+/// [`fees_by_symbol`].
+pub fn total_fees<'a, T: Charged + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> FeeTotal {
+    let mut total = FeeTotal::default();
+    for transaction in transactions {
+        total.add(transaction);
+    }
+    total
+}
+
+/// Sum the fees and the taxes paid over the transactions per
+/// [`charge_symbol`][Charged::charge_symbol].
+pub fn fees_by_symbol<'a, T: Charged + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> HashMap<String, FeeTotal> {
+    let mut totals = HashMap::<String, FeeTotal>::new();
+    for transaction in transactions {
+        totals
+            .entry(transaction.charge_symbol().to_owned())
+            .or_default()
+            .add(transaction);
+    }
+    totals
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ApiKeys, Client};
-    use pretty_assertions::assert_str_eq;
+    use crate::{
+        error::SendRequest,
+        http::{mock::MockHttpClient, OrderType},
+        ApiKeys, Client,
+    };
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use rust_decimal_macros::dec;
 
-    use super::{CryptoTransaction, FiatTransaction, TradeTransaction};
+    use super::{
+        fees_by_symbol, total_fees, CryptoTransaction, FeeTotal,
+        FiatTransaction, TradeTransaction,
+    };
 
     #[ignore]
     #[async_std::test]
@@ -290,7 +452,7 @@ mod tests {
 
         let transactions = Client::new(Some(keys), None)
             .unwrap()
-            .trade_transactions(None, None, vec!["try"], None)
+            .trade_transactions(None, None, vec!["try"], ..)
             .await
             .unwrap();
         for transaction in transactions {
@@ -307,7 +469,7 @@ mod tests {
 
         let transactions = Client::new(Some(keys), None)
             .unwrap()
-            .crypto_transactions(None, vec!["btc"], None)
+            .crypto_transactions(None, vec!["btc"], ..)
             .await
             .unwrap();
         for transaction in transactions {
@@ -324,7 +486,7 @@ mod tests {
 
         let transactions = Client::new(Some(keys), None)
             .unwrap()
-            .fiat_transactions(None, vec!["try"], None)
+            .fiat_transactions(None, vec!["try"], ..)
             .await
             .unwrap();
         for transaction in transactions {
@@ -349,4 +511,90 @@ mod tests {
         let json_string = include_str!("fiat_sample.json");
         serde_json::from_str::<Vec<FiatTransaction>>(json_string).unwrap();
     }
+
+    #[test]
+    fn sum_fees() {
+        let trades = serde_json::from_str::<Vec<TradeTransaction>>(
+            include_str!("trade_sample.json"),
+        )
+        .unwrap();
+        let total = total_fees(&trades);
+        assert_eq!(
+            total,
+            FeeTotal {
+                fee: dec!(0.73095036),
+                tax: dec!(0.13157106),
+            }
+        );
+        assert_eq!(total.total(), dec!(0.86252142));
+        assert_eq!(fees_by_symbol(&trades)["TRY"], total);
+
+        let mut crypto = serde_json::from_str::<Vec<CryptoTransaction>>(
+            include_str!("crypto_sample.json"),
+        )
+        .unwrap();
+        crypto.push(crypto[0].clone());
+        crypto[1].currency_symbol = "BTC".to_owned();
+        crypto[1].fee = dec!(-0.0001);
+        let totals = fees_by_symbol(&crypto);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals["XLM"].fee, dec!(0.02));
+        assert_eq!(totals["BTC"].fee, dec!(0.0001));
+
+        let fiat = serde_json::from_str::<Vec<FiatTransaction>>(include_str!(
+            "fiat_sample.json"
+        ))
+        .unwrap();
+        assert_eq!(total_fees(&fiat).total(), dec!(0));
+    }
+
+    #[async_std::test]
+    async fn send_trade_transaction_filters() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        for _ in 0..3 {
+            backend.push_data(include_str!("trade_sample.json"));
+        }
+        client
+            .trade_transactions(
+                None,
+                Some(OrderType::Buy),
+                vec!["btc", "try"],
+                ..,
+            )
+            .await
+            .unwrap();
+        client
+            .trade_transactions(None, None, Vec::<String>::new(), ..)
+            .await
+            .unwrap();
+        client
+            .trade_transactions(Some(5), Some(OrderType::Sell), vec!["btc"], ..)
+            .await
+            .unwrap();
+        let requests = backend.requests();
+        assert_eq!(
+            requests[0].url.query(),
+            Some("symbol=btc&symbol=try&type=buy")
+        );
+        assert_eq!(requests[1].url.query(), Some(""));
+        assert_eq!(requests[2].url.query(), Some("orderId=5"));
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[async_std::test]
+    async fn reject_inverted_date_range() {
+        let backend = MockHttpClient::new();
+        let client = backend.client_with_keys();
+        let error = client
+            .crypto_transactions(None, Vec::<String>::new(), 20..10)
+            .await
+            .unwrap_err();
+        let SendRequest::ParameterError { source } = error else {
+            panic!("unexpected error: `{error}`");
+        };
+        assert_eq!(source.name(), "date_range");
+        assert_eq!(source.value(), "20..10");
+        assert!(backend.requests().is_empty());
+    }
 }
@@ -1,15 +1,22 @@
 //! Implementation of the exchange info endpoint.
 
-use rust_decimal::Decimal;
-use serde::Deserialize;
+use std::{
+    fmt::Display,
+    sync::Arc,
+    time::{SystemTime, SystemTimeError, UNIX_EPOCH},
+};
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{de::Error, Deserialize, Deserializer};
+use serde_json::Value;
 use surf::http::Method;
 
 use crate::{
-    error::{Parse, SendRequest},
-    http::{request::Parameters, Client, OrderMethod, Request},
+    error::{Parameter, Parse, SendRequest},
+    http::{request::Parameters, Client, Data, OrderMethod, Request},
 };
 
-impl Client<'_> {
+impl Client {
     /// Gets a list of all known currencies.
     /// You can use this endpoint to get all tradable pairs and their quantity
     /// or price scales.
@@ -30,6 +37,82 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Same as [`exchange_info`][Self::exchange_info] but reuses the last
+    /// received exchange info until it gets older than the time to live set
+    /// by [`exchange_info_ttl`]. Clones of a client share the cached exchange
+    /// info.
+    ///
+    /// [`exchange_info_ttl`]: crate::http::ClientBuilder::exchange_info_ttl
+    /// # Errors
+    /// [`SendRequest`] if the cached exchange info is missing or expired and
+    /// there is an error sending the request or there is an error or a
+    /// malformation in the received response.
+    ///
+    /// See also <https://docs.btcturk.com/public-endpoints/exchange-info>.
+    pub async fn exchange_info_cached(
+        &self,
+    ) -> Result<Arc<ExchangeInfo>, SendRequest> {
+        if let Some(exchange_info) = self.exchange_info_cache().get() {
+            return Ok(exchange_info);
+        }
+        let exchange_info = self.exchange_info().await?;
+        Ok(self.exchange_info_cache().set(exchange_info))
+    }
+
+    /// Get the normalized names, e.g. `BTC_TRY`, of the pairs which are open
+    /// for trading. Based on the
+    /// [`exchange_info_cached`][Self::exchange_info_cached].
+    /// # Errors
+    /// [`SendRequest`] if the cached exchange info is missing or expired and
+    /// there is an error sending the request or there is an error or a
+    /// malformation in the received response.
+    pub async fn tradable_pairs(&self) -> Result<Vec<String>, SendRequest> {
+        Ok(self
+            .exchange_info_cached()
+            .await?
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.is_trading())
+            .map(|symbol| symbol.name_normalized.clone())
+            .collect())
+    }
+
+    /// Get the current time of the server in milliseconds. The exchange info
+    /// is requested every time instead of using the cached one.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    pub async fn server_time(&self) -> Result<u64, SendRequest> {
+        Ok(self.exchange_info().await?.server_time)
+    }
+
+    /// Estimate how many milliseconds the local clock is ahead of the server
+    /// clock. The offset is negative if the local clock is behind.
+    ///
+    /// The private endpoints are signed with the local time so they may
+    /// reject the requests if the offset is large. The server time is
+    /// compared with the local time halfway through the request, so the
+    /// estimate is off by up to half of the round trip time.
+    /// # Errors
+    /// [`SendRequest`] if there is an error retrieving the local time or
+    /// sending the request or there is an error or a malformation in the
+    /// received response.
+    pub async fn clock_offset(&self) -> Result<i64, SendRequest> {
+        let sent_at = unix_millis()?;
+        let server_time = self.server_time().await?;
+        let received_at = unix_millis()?;
+        let local_time = sent_at + received_at.saturating_sub(sent_at) / 2;
+        // Millisecond timestamps fit in `i64` for millions of years.
+        #[allow(clippy::cast_possible_wrap)]
+        Ok(local_time as i64 - server_time as i64)
+    }
+}
+
+/// Local time in milliseconds.
+fn unix_millis() -> Result<u64, SystemTimeError> {
+    let duration = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    Ok(duration.as_secs() * 1000 + u64::from(duration.subsec_millis()))
 }
 
 /// **Sample**:
@@ -39,6 +122,7 @@ impl Client<'_> {
 /// See also <https://docs.btcturk.com/public-endpoints/exchange-info>
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ExchangeInfo {
     #[allow(missing_docs)]
     #[serde(rename = "timeZone")]
@@ -53,10 +137,67 @@ pub struct ExchangeInfo {
     pub currency_operation_blocks: Vec<CurrencyOperationBlock>,
 }
 
+impl Data for ExchangeInfo {}
+
+impl ExchangeInfo {
+    /// Find a pair by its name, e.g. `BTCTRY`, or its normalized name, e.g.
+    /// `BTC_TRY`. The case of the name is ignored.
+    #[must_use]
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| {
+            symbol.name.eq_ignore_ascii_case(name)
+                || symbol.name_normalized.eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Find a currency by its symbol, e.g. `BTC`. The case of the symbol is
+    /// ignored.
+    #[must_use]
+    pub fn currency(&self, symbol: &str) -> Option<&Currency> {
+        self.currencies
+            .iter()
+            .find(|currency| currency.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    /// Check whether the withdrawals of a currency are blocked, e.g. `BTC`.
+    /// Returns `false` if the currency isn't in the
+    /// [`currency_operation_blocks`][Self::currency_operation_blocks].
+    #[must_use]
+    pub fn is_withdrawal_disabled(&self, symbol: &str) -> bool {
+        self.operation_block(symbol)
+            .is_some_and(|block| block.withdrawal_disabled)
+    }
+
+    /// Check whether the deposits of a currency are blocked, e.g. `BTC`.
+    /// Returns `false` if the currency isn't in the
+    /// [`currency_operation_blocks`][Self::currency_operation_blocks].
+    #[must_use]
+    pub fn is_deposit_disabled(&self, symbol: &str) -> bool {
+        self.operation_block(symbol)
+            .is_some_and(|block| block.deposit_disabled)
+    }
+
+    fn operation_block(&self, symbol: &str) -> Option<&CurrencyOperationBlock> {
+        self.currency_operation_blocks
+            .iter()
+            .find(|block| block.currency_symbol.eq_ignore_ascii_case(symbol))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ExchangeInfo {
+    /// Get [`server_time`][Self::server_time] as a date and time in UTC.
+    #[must_use]
+    pub fn server_datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.server_time)
+    }
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[allow(missing_docs)]
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Symbol {
     #[allow(missing_docs)]
     pub id: i64,
@@ -65,7 +206,7 @@ pub struct Symbol {
     #[allow(missing_docs)]
     pub name_normalized: String,
     #[allow(missing_docs)]
-    pub status: String,
+    pub status: SymbolStatus,
     #[allow(missing_docs)]
     pub numerator: String,
     #[allow(missing_docs)]
@@ -82,9 +223,13 @@ pub struct Symbol {
     pub order_methods: Vec<OrderMethod>,
     #[allow(missing_docs)]
     pub display_format: String,
-    #[allow(missing_docs)]
+    /// Whether the trading fees are charged in the
+    /// [`numerator`][Self::numerator] instead of the
+    /// [`denominator`][Self::denominator]. See
+    /// [`commission_symbol`][Self::commission_symbol].
     pub commission_from_numerator: bool,
-    #[allow(missing_docs)]
+    /// Position the exchange lists the pair at. See
+    /// [`sort_by_display_order`][crate::http::sort_by_display_order].
     pub order: i64,
     #[allow(missing_docs)]
     pub price_rounding: bool,
@@ -96,12 +241,111 @@ pub struct Symbol {
     pub maximum_order_amount: Option<Decimal>,
 }
 
-#[allow(missing_docs)]
-#[derive(
-    Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-#[serde(tag = "filterType")]
+impl Symbol {
+    /// Check whether the pair is open for trading, i.e. its
+    /// [`status`][Self::status] is [`Trading`][SymbolStatus::Trading].
+    #[must_use]
+    pub fn is_trading(&self) -> bool {
+        self.status == SymbolStatus::Trading
+    }
+
+    /// Get the symbol of the currency the trading fees are charged in, i.e.
+    /// the [`numerator`][Self::numerator] if
+    /// [`commission_from_numerator`][Self::commission_from_numerator] is set
+    /// and the [`denominator`][Self::denominator] otherwise.
+    ///
+    /// The fee rates are not provided by the API. They depend on the trading
+    /// volume of the account and can be derived from the `fee` of the past
+    /// [`trade_transactions`][crate::Client::trade_transactions].
+    #[must_use]
+    pub fn commission_symbol(&self) -> &str {
+        if self.commission_from_numerator {
+            &self.numerator
+        } else {
+            &self.denominator
+        }
+    }
+
+    /// Round the price down to the
+    /// [`denominator_scale`][Self::denominator_scale] and then down to a
+    /// multiple of the `tick_size` of the [`PriceFilter`][Filter::PriceFilter],
+    /// if any. Use it to avoid the orders being rejected for having too many
+    /// decimals.
+    #[must_use]
+    pub fn round_price(&self, price: Decimal) -> Decimal {
+        let price = round_down(price, self.denominator_scale);
+        match self.tick_size() {
+            Some(tick_size) if !tick_size.is_zero() => {
+                (price / tick_size).trunc() * tick_size
+            }
+            _ => price,
+        }
+    }
+
+    /// Round the quantity down to the
+    /// [`numerator_scale`][Self::numerator_scale].
+    #[must_use]
+    pub fn round_quantity(&self, quantity: Decimal) -> Decimal {
+        round_down(quantity, self.numerator_scale)
+    }
+
+    /// Check the parameters of an order against the limits of the symbol.
+    /// The exchange value of the order, i.e. price times quantity, must not
+    /// be less than the `min_exchange_value` of the
+    /// [`PriceFilter`][Filter::PriceFilter], if any. Orders without a price,
+    /// e.g. market orders, are not checked.
+    /// # Errors
+    /// [`Parameter`] named `exchange_value` with the exchange value of the
+    /// order if it is too low.
+    pub fn validate_order(
+        &self,
+        price: Option<Decimal>,
+        quantity: Decimal,
+    ) -> Result<(), Parameter> {
+        let (Some(price), Some(min_exchange_value)) =
+            (price, self.min_exchange_value())
+        else {
+            return Ok(());
+        };
+        let exchange_value = price * quantity;
+        if exchange_value < min_exchange_value {
+            return Err(Parameter::new(
+                "exchange_value",
+                exchange_value.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn tick_size(&self) -> Option<Decimal> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::PriceFilter { tick_size, .. } => Some(*tick_size),
+            Filter::Unknown(_) => None,
+        })
+    }
+
+    fn min_exchange_value(&self) -> Option<Decimal> {
+        self.filters.iter().find_map(|filter| match filter {
+            Filter::PriceFilter {
+                min_exchange_value, ..
+            } => Some(*min_exchange_value),
+            Filter::Unknown(_) => None,
+        })
+    }
+}
+
+fn round_down(value: Decimal, scale: u64) -> Decimal {
+    let scale = u32::try_from(scale).unwrap_or(u32::MAX);
+    value.round_dp_with_strategy(scale, RoundingStrategy::ToZero)
+}
+
+/// Filter of a [`Symbol`]. Filters of a type which is not known by this crate
+/// are kept as [`Unknown`][Self::Unknown] instead of failing the
+/// deserialization of the whole exchange info.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(remote = "Self", tag = "filterType")]
 pub enum Filter {
+    /// Limits of the price and the exchange value of the orders.
     #[serde(rename = "PRICE_FILTER")]
     #[serde(rename_all = "camelCase")]
     PriceFilter {
@@ -118,12 +362,43 @@ pub enum Filter {
         #[allow(missing_docs)]
         max_amount: Option<Decimal>,
     },
+    /// JSON text of a filter whose `filterType` is not known.
+    #[serde(skip_deserializing)]
+    Unknown(String),
+}
+
+impl Filter {
+    /// Get the JSON value of an [`Unknown`][Self::Unknown] filter, e.g.
+    /// `{"filterType": "LOT_SIZE", ...}`.
+    #[must_use]
+    pub fn raw(&self) -> Option<Value> {
+        match self {
+            Self::Unknown(json) => serde_json::from_str(json).ok(),
+            Self::PriceFilter { .. } => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        match value.get("filterType").and_then(Value::as_str) {
+            // Calls the derived implementation.
+            Some("PRICE_FILTER") => {
+                Self::deserialize(value).map_err(D::Error::custom)
+            }
+            _ => Ok(Self::Unknown(value.to_string())),
+        }
+    }
 }
 
 #[allow(clippy::struct_excessive_bools)]
 #[allow(missing_docs)]
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Currency {
     #[allow(missing_docs)]
     pub id: i64,
@@ -155,11 +430,48 @@ pub struct Currency {
     pub is_new: bool,
 }
 
+impl Currency {
+    /// Check the parameters of a withdrawal against the limits of the
+    /// currency. The amount must not be less than the
+    /// [`min_withdrawal`][Self::min_withdrawal], the address and the tag
+    /// lengths must be within the limits and a tag can only be passed if the
+    /// currency supports it.
+    /// # Errors
+    /// [`Parameter`] naming the first parameter which is out of the limits.
+    pub fn validate_withdrawal(
+        &self,
+        amount: Decimal,
+        address: &str,
+        tag: Option<&str>,
+    ) -> Result<(), Parameter> {
+        if amount < self.min_withdrawal {
+            return Err(Parameter::new("amount", amount.to_string()));
+        }
+        if !within(address.len(), self.address.min_len, self.address.max_len) {
+            return Err(Parameter::new("address", address.to_owned()));
+        }
+        if let Some(tag) = tag {
+            if !self.tag.enable
+                || !within(tag.len(), self.tag.min_len, self.tag.max_len)
+            {
+                return Err(Parameter::new("tag", tag.to_owned()));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn within(len: usize, min: Option<u64>, max: Option<u64>) -> bool {
+    let len = len as u64;
+    min.is_none_or(|min| len >= min) && max.is_none_or(|max| len <= max)
+}
+
 #[allow(missing_docs)]
 #[derive(
     Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Address {
     #[allow(missing_docs)]
     pub min_len: Option<u64>,
@@ -167,32 +479,126 @@ pub struct Address {
     pub max_len: Option<u64>,
 }
 
-#[allow(missing_docs)]
-#[derive(
-    Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-#[serde(rename_all = "camelCase")]
-#[serde(try_from = "String")]
+/// Status of a symbol. A status which is not known by this crate is kept as
+/// [`Unknown`][Self::Unknown] when received from the API.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SymbolStatus {
+    /// Orders can be submitted.
+    Trading,
+    /// Trading is stopped for a while.
+    Halted,
+    /// Symbol is removed from the exchange.
+    Delisted,
+    /// Status which is not known by this crate. Holds the received string.
+    Unknown(String),
+}
+
+impl SymbolStatus {
+    /// Get the received string if the status is [`Unknown`][Self::Unknown].
+    #[must_use]
+    pub fn unknown_value(&self) -> Option<&str> {
+        match self {
+            Self::Unknown(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "trading" | "Trading" | "TRADING" => Self::Trading,
+            "halted" | "Halted" | "HALTED" | "halt" | "Halt" | "HALT" => {
+                Self::Halted
+            }
+            "delisted" | "Delisted" | "DELISTED" => Self::Delisted,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for SymbolStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Trading => "TRADING",
+            Self::Halted => "HALTED",
+            Self::Delisted => "DELISTED",
+            Self::Unknown(value) => value,
+        })
+    }
+}
+
+impl TryFrom<String> for SymbolStatus {
+    type Error = Parse;
+
+    /// Unlike the deserialization, unknown statuses are rejected.
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+            .ok_or_else(|| Parse::new(value, "&str", "SymbolStatus"))
+    }
+}
+
+impl<'de> Deserialize<'de> for SymbolStatus {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Self::parse(&value).unwrap_or(Self::Unknown(value)))
+    }
+}
+
+/// Type of a currency. A type which is not known by this crate is kept as
+/// [`Unknown`][Self::Unknown] when received from the API.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CurrencyType {
+    #[allow(missing_docs)]
     Crypto,
+    #[allow(missing_docs)]
     Fiat,
+    /// Type which is not known by this crate. Holds the received string.
+    Unknown(String),
+}
+
+impl CurrencyType {
+    /// Get the received string if the type is [`Unknown`][Self::Unknown].
+    #[must_use]
+    pub fn unknown_value(&self) -> Option<&str> {
+        match self {
+            Self::Unknown(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "crypto" | "Crypto" | "CRYPTO" => Self::Crypto,
+            "fiat" | "Fiat" | "FIAT" => Self::Fiat,
+            _ => return None,
+        })
+    }
 }
 
 impl TryFrom<String> for CurrencyType {
     type Error = Parse;
 
+    /// Unlike the deserialization, unknown types are rejected.
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        match value.as_ref() {
-            "crypto" | "Crypto" | "CRYPTO" => Ok(Self::Crypto),
-            "fiat" | "Fiat" | "FIAT" => Ok(Self::Fiat),
-            other => Err(Parse::new(other, "&str", "CurrencyType")),
-        }
+        Self::parse(&value)
+            .ok_or_else(|| Parse::new(value, "&str", "CurrencyType"))
+    }
+}
+
+impl<'de> Deserialize<'de> for CurrencyType {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Self::parse(&value).unwrap_or(Self::Unknown(value)))
     }
 }
 
 #[allow(missing_docs)]
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Tag {
     #[allow(missing_docs)]
     pub enable: bool,
@@ -207,6 +613,7 @@ pub struct Tag {
 #[allow(missing_docs)]
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CurrencyOperationBlock {
     #[allow(missing_docs)]
     pub currency_symbol: String,
@@ -218,9 +625,21 @@ pub struct CurrencyOperationBlock {
 
 #[cfg(test)]
 mod tests {
-    use crate::http::Client;
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use rust_decimal_macros::dec;
+
+    use crate::http::{mock::MockHttpClient, Client};
 
-    use super::{ExchangeInfo, Filter};
+    use super::{ExchangeInfo, Filter, Symbol, SymbolStatus};
+
+    fn btc_try() -> Symbol {
+        let json_string = include_str!("sample.json");
+        let exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        exchange_info.symbols.into_iter().next().unwrap()
+    }
 
     #[ignore]
     #[async_std::test]
@@ -246,6 +665,7 @@ mod tests {
                 } => {
                     assert!(max_price >= min_price);
                 }
+                Filter::Unknown(_) => {}
             }
         }
     }
@@ -255,4 +675,205 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
     }
+
+    #[test]
+    fn deserialize_symbol_status() {
+        let json_string = include_str!("sample.json");
+        let exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        assert!(matches!(
+            exchange_info.symbols[0].status,
+            SymbolStatus::Trading
+        ));
+        let statuses = serde_json::from_str::<Vec<SymbolStatus>>(
+            r#"["HALTED", "DELISTED", "PAUSED"]"#,
+        )
+        .unwrap();
+        assert_eq!(statuses[0], SymbolStatus::Halted);
+        assert_eq!(statuses[1], SymbolStatus::Delisted);
+        assert_eq!(statuses[2].unknown_value(), Some("PAUSED"));
+        assert_eq!(statuses[2].to_string(), "PAUSED");
+        assert!(SymbolStatus::try_from("PAUSED".to_owned()).is_err());
+    }
+
+    #[test]
+    fn keep_unknown_filter() {
+        let filters = serde_json::from_str::<Vec<Filter>>(
+            r#"[
+                {"filterType": "LOT_SIZE", "stepSize": "0.1"},
+                {
+                    "filterType": "PRICE_FILTER",
+                    "minPrice": "1",
+                    "maxPrice": "100",
+                    "tickSize": "1",
+                    "minExchangeValue": "10"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let raw = filters[0].raw().unwrap();
+        assert_eq!(raw["filterType"], "LOT_SIZE");
+        assert_eq!(raw["stepSize"], "0.1");
+        assert!(matches!(filters[1], Filter::PriceFilter { .. }));
+        assert_eq!(filters[1].raw(), None);
+
+        let error = serde_json::from_str::<Filter>(
+            r#"{"filterType": "PRICE_FILTER", "minPrice": "1"}"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("maxPrice"), "{error}");
+    }
+
+    fn exchange_info_backend(responses: usize) -> MockHttpClient {
+        let backend = MockHttpClient::new();
+        for _ in 0..responses {
+            backend.push_data(include_str!("sample.json"));
+        }
+        backend
+    }
+
+    #[async_std::test]
+    async fn reuse_cached_exchange_info() {
+        let backend = exchange_info_backend(1);
+        let client = Client::with_http_client(backend.client(), None, None);
+        let first = client.exchange_info_cached().await.unwrap();
+        let second = client.clone().exchange_info_cached().await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(backend.requests().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn list_tradable_pairs() {
+        let backend = MockHttpClient::new();
+        backend.push_data(
+            &include_str!("sample.json").replacen("TRADING", "HALTED", 1),
+        );
+        let client = Client::with_http_client(backend.client(), None, None);
+        let pairs = client.tradable_pairs().await.unwrap();
+        assert_eq!(pairs, vec!["USDT_TRY", "XTZ_BTC"]);
+    }
+
+    #[async_std::test]
+    async fn refetch_expired_exchange_info() {
+        let backend = exchange_info_backend(2);
+        let client = Client::builder()
+            .http_client(backend.client())
+            .exchange_info_ttl(Duration::ZERO)
+            .build()
+            .unwrap();
+        client.exchange_info_cached().await.unwrap();
+        client.exchange_info_cached().await.unwrap();
+        assert_eq!(backend.requests().len(), 2);
+    }
+
+    #[async_std::test]
+    async fn estimate_clock_offset() {
+        let backend = exchange_info_backend(2);
+        let client = Client::with_http_client(backend.client(), None, None);
+        let server_time = client.server_time().await.unwrap();
+        assert_eq!(server_time, 1_641_916_253_216);
+        let offset = client.clock_offset().await.unwrap();
+        let local_time = super::unix_millis().unwrap();
+        #[allow(clippy::cast_possible_wrap)]
+        let expected = local_time as i64 - server_time as i64;
+        assert!((expected - offset).abs() < 1000, "{offset}");
+        assert_eq!(backend.requests().len(), 2);
+    }
+
+    #[test]
+    fn validate_min_exchange_value() {
+        let json_string = include_str!("sample.json");
+        let exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        let btctry = exchange_info.symbol("BTCTRY").unwrap();
+        assert_eq!(
+            btctry.validate_order(Some(dec!(500000)), dec!(0.001)),
+            Ok(())
+        );
+        assert_eq!(btctry.validate_order(None, dec!(0.0000001)), Ok(()));
+        let error = btctry
+            .validate_order(Some(dec!(500000)), dec!(0.0001))
+            .unwrap_err();
+        assert_eq!(error.name(), "exchange_value");
+        assert_eq!(error.value().to_string(), "50.0000");
+    }
+
+    #[test]
+    fn find_symbol_and_currency() {
+        let json_string = include_str!("sample.json");
+        let exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        let by_name = exchange_info.symbol("BTCTRY").unwrap();
+        let by_normalized_name = exchange_info.symbol("btc_try").unwrap();
+        assert_eq!(by_name, by_normalized_name);
+        assert!(exchange_info.symbol("BTC").is_none());
+        assert_eq!(exchange_info.currency("btc").unwrap().symbol, "BTC");
+        assert!(exchange_info.currency("BTCTRY").is_none());
+    }
+
+    #[test]
+    fn get_commission_symbol() {
+        let mut symbol = btc_try();
+        assert_eq!(symbol.commission_symbol(), "TRY");
+        symbol.commission_from_numerator = true;
+        assert_eq!(symbol.commission_symbol(), "BTC");
+    }
+
+    #[test]
+    fn check_operation_blocks() {
+        let json_string = include_str!("sample.json");
+        let mut exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        exchange_info.currency_operation_blocks[0].withdrawal_disabled = true;
+        assert!(exchange_info.is_withdrawal_disabled("BTC"));
+        assert!(!exchange_info.is_deposit_disabled("BTC"));
+        assert!(!exchange_info.is_withdrawal_disabled("ETH"));
+        assert!(!exchange_info.is_withdrawal_disabled("UNKNOWN"));
+    }
+
+    #[test]
+    fn validate_withdrawal() {
+        let json_string = include_str!("sample.json");
+        let exchange_info =
+            serde_json::from_str::<ExchangeInfo>(json_string).unwrap();
+        let currency = |symbol| exchange_info.currency(symbol).unwrap();
+        let btc = currency("BTC");
+        let address = "1BoatSLRHtKNngkdXEeobR76b53LETtpyT";
+        assert_eq!(btc.validate_withdrawal(dec!(0.001), address, None), Ok(()));
+        let error = btc.validate_withdrawal(dec!(0.0001), address, None);
+        assert_eq!(error.unwrap_err().name(), "amount");
+        let error = btc.validate_withdrawal(dec!(0.001), "1Boat", None);
+        assert_eq!(error.unwrap_err().name(), "address");
+        let error = btc.validate_withdrawal(dec!(0.001), address, Some("1"));
+        assert_eq!(error.unwrap_err().name(), "tag");
+
+        let eos = currency("EOS");
+        let result =
+            eos.validate_withdrawal(dec!(5), "abcdefghijkl", Some("1"));
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn round_price_to_tick_size() {
+        let symbol = btc_try();
+        assert_eq!(symbol.round_price(dec!(500_009.999)), dec!(500_000));
+        assert_eq!(symbol.round_price(dec!(500_010)), dec!(500_010));
+    }
+
+    #[test]
+    fn round_price_to_scale() {
+        let mut symbol = btc_try();
+        symbol.filters.clear();
+        assert_eq!(symbol.round_price(dec!(500_009.999)), dec!(500_009.99));
+    }
+
+    #[test]
+    fn round_quantity_to_scale() {
+        let symbol = btc_try();
+        assert_eq!(
+            symbol.round_quantity(dec!(0.123_456_789)),
+            dec!(0.123_456_78)
+        );
+        assert_eq!(symbol.round_quantity(dec!(1)), dec!(1));
+    }
 }
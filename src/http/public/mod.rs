@@ -1,7 +1,7 @@
 //! Implementation of public endpoint items for [`Client`][super::Client].
 
 pub mod ohlc;
-pub use ohlc::Ohlc;
+pub use ohlc::{Ohlc, OhlcResolution};
 
 pub mod order_book;
 pub use order_book::OrderBook;
@@ -1,17 +1,22 @@
 //! Implementation of the OHLC endpoint.
 
-use std::ops::Range;
+use std::{
+    ops::RangeBounds,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
+use futures::{stream, StreamExt};
+use serde::Deserialize;
 use surf::http::Method;
 
 use rust_decimal::Decimal;
 
 use crate::{
-    error::SendRequest,
-    http::{request::Parameters, Client, Request},
+    error::{Response, SendRequest},
+    http::{request::Parameters, Client, Data, Request},
 };
 
-impl Client<'_> {
+impl Client {
     /// Returns daily cumulative data.
     ///
     /// This is the data that is shown in our charting interface.
@@ -22,8 +27,9 @@ impl Client<'_> {
     /// # Parameters
     /// - `pair`: For example, `BTCUSDT`.
     /// - `range`: This is the combination of `from` and `to` parameters.
-    /// The range is UNIX time in **seconds**. An example range is
-    /// 1321234542..143143265.
+    ///   The range is UNIX time in **seconds**. An example range is
+    ///   1321234542..143143265. An unbounded side is left out, e.g. pass `..`
+    ///   to send neither.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
     /// is an error or a malformation in the received response.
@@ -32,14 +38,11 @@ impl Client<'_> {
     pub async fn ohlc(
         &self,
         pair: impl Into<String> + Send,
-        range: Option<Range<u64>>,
+        range: impl RangeBounds<u64> + Send,
     ) -> Result<Vec<Ohlc>, SendRequest> {
         let mut parameters = Parameters::new();
         parameters.push_string("pair", Some(pair.into()));
-        if let Some(range) = range {
-            parameters.push_number("from", Some(range.start));
-            parameters.push_number("to", Some(range.end));
-        }
+        parameters.push_range("range", ("from", "to"), range)?;
         self.send(
             Request {
                 endpoint: self.url_cache().ohlc(),
@@ -51,6 +54,219 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Same as [`ohlc`][Self::ohlc] but gets the candles of many pairs
+    /// concurrently. At most `concurrency` requests are in flight at a time.
+    /// Use [`rate_limit`][crate::http::ClientBuilder::rate_limit] to space
+    /// them out further.
+    ///
+    /// Returns the result of each pair in the order of the pairs. A failed
+    /// pair doesn't stop the others.
+    /// # Parameters
+    /// - `pairs`: For example, `["BTCUSDT", "ETHUSDT"]`.
+    /// - `range`: Range of each request. See [`ohlc`][Self::ohlc].
+    /// - `concurrency`: Maximum number of concurrent requests. `0` is treated
+    ///   as `1`.
+    pub async fn ohlc_many(
+        &self,
+        pairs: &[&str],
+        range: impl RangeBounds<u64> + Send,
+        concurrency: usize,
+    ) -> Vec<(String, Result<Vec<Ohlc>, SendRequest>)> {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        stream::iter(pairs.iter().map(|pair| (*pair).to_owned()))
+            .map(|pair| async move {
+                let ohlc = self.ohlc(pair.clone(), range).await;
+                (pair, ohlc)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Returns the latest `count` daily candles, including the candle of
+    /// today so far, in ascending order of time. The range is computed from
+    /// the local time so there is no need to pass one to [`ohlc`][Self::ohlc].
+    ///
+    /// One more day than `count` is requested to cover the partial candle of
+    /// today. Fewer candles are returned if the pair has less history.
+    /// # Parameters
+    /// - `pair`: For example, `BTCUSDT`.
+    /// - `count`: Number of candles. No request is sent if it is zero.
+    /// # Errors
+    /// [`SendRequest`] if there is an error retrieving the local time or
+    /// sending the request or there is an error or a malformation in the
+    /// received response.
+    pub async fn ohlc_latest(
+        &self,
+        pair: impl Into<String> + Send,
+        count: usize,
+    ) -> Result<Vec<Ohlc>, SendRequest> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let days = u64::try_from(count).unwrap_or(u64::MAX).saturating_add(1);
+        let from = now.saturating_sub(days.saturating_mul(SECONDS_PER_DAY));
+        let mut ohlc = self.ohlc(pair, from..now).await?;
+        ohlc.sort_by_key(|ohlc| ohlc.time);
+        let skipped = ohlc.len().saturating_sub(count);
+        ohlc.drain(..skipped);
+        Ok(ohlc)
+    }
+
+    /// Returns the candles of the given resolution, e.g. hourly candles for
+    /// intraday analysis. See [`ohlc`][Self::ohlc] for the daily candles.
+    ///
+    /// The candles are received from the kline endpoint which doesn't return
+    /// the [`total`][Ohlc::total] and the [`average`][Ohlc::average] so they
+    /// are set to zero. The daily change fields hold the change from the
+    /// open to the close of each candle.
+    /// # Parameters
+    /// - `pair`: For example, `BTCUSDT`.
+    /// - `range`: This is the combination of `from` and `to` parameters.
+    ///   The range is UNIX time in **seconds**. See [`ohlc`][Self::ohlc].
+    /// - `resolution`: Time span of each candle.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    pub async fn ohlc_with_resolution(
+        &self,
+        pair: impl Into<String> + Send,
+        range: impl RangeBounds<u64> + Send,
+        resolution: OhlcResolution,
+    ) -> Result<Vec<Ohlc>, SendRequest> {
+        let pair = pair.into();
+        let mut parameters = Parameters::new();
+        parameters.push_string("symbol", Some(pair.clone()));
+        parameters.push_number("resolution", Some(resolution.minutes()));
+        parameters.push_range("range", ("from", "to"), range)?;
+        let klines: Klines = self
+            .send(
+                Request {
+                    endpoint: self.url_cache().klines(),
+                    method: Method::Get,
+                    parameters,
+                    requires_auth: false,
+                },
+                true,
+            )
+            .await?;
+        Ok(klines.into_ohlc(&pair)?)
+    }
+}
+
+/// Time span of each daily candle.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Time span of each candle returned by
+/// [`ohlc_with_resolution`][Client::ohlc_with_resolution].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OhlcResolution {
+    #[allow(missing_docs)]
+    OneMinute,
+    #[allow(missing_docs)]
+    FiveMinutes,
+    #[allow(missing_docs)]
+    FifteenMinutes,
+    #[allow(missing_docs)]
+    ThirtyMinutes,
+    #[allow(missing_docs)]
+    OneHour,
+    #[allow(missing_docs)]
+    FourHours,
+    #[allow(missing_docs)]
+    OneDay,
+    #[allow(missing_docs)]
+    OneWeek,
+}
+
+impl OhlcResolution {
+    /// Get the resolution in minutes as expected by the endpoint.
+    #[must_use]
+    pub const fn minutes(self) -> u32 {
+        match self {
+            Self::OneMinute => 1,
+            Self::FiveMinutes => 5,
+            Self::FifteenMinutes => 15,
+            Self::ThirtyMinutes => 30,
+            Self::OneHour => 60,
+            Self::FourHours => 240,
+            Self::OneDay => 1440,
+            Self::OneWeek => 10080,
+        }
+    }
+}
+
+/// Candles in columns. The columns are missing if there is no data in the
+/// range. The status is `ok`, `no_data` or `error`.
+///
+/// **Sample**:
+/// ```json
+#[doc = include_str!("klines_sample.json")]
+/// ```
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+struct Klines {
+    #[serde(rename = "s")]
+    status: String,
+    #[serde(rename = "errmsg", default)]
+    message: Option<String>,
+    #[serde(rename = "t", default)]
+    time: Vec<u64>,
+    #[serde(rename = "o", default)]
+    open: Vec<Decimal>,
+    #[serde(rename = "h", default)]
+    high: Vec<Decimal>,
+    #[serde(rename = "l", default)]
+    low: Vec<Decimal>,
+    #[serde(rename = "c", default)]
+    close: Vec<Decimal>,
+    #[serde(rename = "v", default)]
+    volume: Vec<Decimal>,
+}
+
+impl Data for Klines {}
+
+impl Klines {
+    /// Put the columns together as candles.
+    fn into_ohlc(self, pair: &str) -> Result<Vec<Ohlc>, Response> {
+        if self.status == "error" {
+            return Err(Response::KlineError {
+                message: self.message,
+            });
+        }
+        let len = self.time.len();
+        if [&self.open, &self.high, &self.low, &self.close, &self.volume]
+            .iter()
+            .any(|column| column.len() != len)
+        {
+            return Err(Response::KlineColumnMismatch);
+        }
+        Ok((0..len)
+            .map(|index| {
+                let open = self.open[index];
+                let close = self.close[index];
+                let change = close - open;
+                Ohlc {
+                    pair: pair.to_owned(),
+                    time: self.time[index],
+                    open,
+                    high: self.high[index],
+                    low: self.low[index],
+                    close,
+                    volume: self.volume[index],
+                    total: Decimal::ZERO,
+                    average: Decimal::ZERO,
+                    daily_change_amount: change,
+                    daily_change_percentage: if open.is_zero() {
+                        Decimal::ZERO
+                    } else {
+                        (change / open * Decimal::ONE_HUNDRED).round_dp(2)
+                    },
+                }
+            })
+            .collect())
+    }
 }
 
 /// **Sample**:
@@ -62,6 +278,7 @@ impl Client<'_> {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Ohlc {
     #[allow(missing_docs)]
     pub pair: String,
@@ -87,13 +304,27 @@ pub struct Ohlc {
     pub daily_change_percentage: Decimal,
 }
 
+#[cfg(feature = "chrono")]
+impl Ohlc {
+    /// Get [`time`][Self::time] as a date and time in UTC. The raw field is
+    /// in seconds.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_secs(self.time)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use super::Ohlc;
-    use crate::http::Client;
+    use super::{Klines, Ohlc, OhlcResolution};
+    use crate::{
+        error::Response,
+        http::{mock::MockHttpClient, Client},
+    };
     use pretty_assertions::assert_eq;
+    use rust_decimal_macros::dec;
 
     #[ignore]
     #[async_std::test]
@@ -106,7 +337,7 @@ mod tests {
         // The given data is daily.
         let data_vector = Client::new(None, None)
             .unwrap()
-            .ohlc("BTCUSDT", Some(current_seconds - 86400..current_seconds))
+            .ohlc("BTCUSDT", current_seconds - 86400..current_seconds)
             .await
             .unwrap();
         assert_eq!(data_vector.len(), 2);
@@ -117,4 +348,101 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<Ohlc>(json_string).unwrap();
     }
+
+    #[test]
+    fn deserialize_klines() {
+        let json_string = include_str!("klines_sample.json");
+        let ohlc = serde_json::from_str::<Klines>(json_string)
+            .unwrap()
+            .into_ohlc("BTCUSDT")
+            .unwrap();
+        assert_eq!(ohlc.len(), 2);
+        assert_eq!(ohlc[1].time, 1_643_850_000);
+        assert_eq!(ohlc[1].close, dec!(36550.5));
+        assert_eq!(ohlc[1].volume, dec!(10.25));
+        assert_eq!(ohlc[0].daily_change_amount, dec!(150));
+        assert_eq!(ohlc[0].daily_change_percentage, dec!(0.41));
+
+        let no_data = serde_json::from_str::<Klines>(r#"{"s": "no_data"}"#);
+        assert!(no_data.unwrap().into_ohlc("BTCUSDT").unwrap().is_empty());
+
+        let error = serde_json::from_str::<Klines>(
+            r#"{"s": "error", "errmsg": "invalid resolution"}"#,
+        );
+        assert_eq!(
+            error.unwrap().into_ohlc("BTCUSDT"),
+            Err(Response::KlineError {
+                message: Some("invalid resolution".to_owned())
+            })
+        );
+        let short = json_string.replace("[12.5, 10.25]", "[12.5]");
+        let short = serde_json::from_str::<Klines>(&short).unwrap();
+        assert_eq!(
+            short.into_ohlc("BTCUSDT"),
+            Err(Response::KlineColumnMismatch)
+        );
+    }
+
+    #[async_std::test]
+    async fn get_latest_ohlc() {
+        let backend = MockHttpClient::new();
+        let sample = include_str!("sample.json");
+        let later = sample.replace("1639526400", "1639612800");
+        backend.push(surf::StatusCode::Ok, &format!("[{later},{sample}]"));
+        let client = Client::with_http_client(backend.client(), None, None);
+        let ohlc = client.ohlc_latest("BTCUSDT", 1).await.unwrap();
+        assert_eq!(ohlc.len(), 1);
+        assert_eq!(ohlc[0].time, 1_639_612_800);
+        assert!(client.ohlc_latest("BTCUSDT", 0).await.unwrap().is_empty());
+
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 1);
+        let parameter = |name: &str| {
+            requests[0]
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<u64>().ok())
+                .unwrap()
+        };
+        assert_eq!(parameter("to") - parameter("from"), 2 * 86_400);
+    }
+
+    #[async_std::test]
+    async fn get_ohlc_of_many_pairs() {
+        let backend = MockHttpClient::new();
+        let sample = include_str!("sample.json");
+        backend
+            .push(surf::StatusCode::Ok, &format!("[{sample}]"))
+            .push(surf::StatusCode::BadRequest, "{}")
+            .push(surf::StatusCode::Ok, "[]");
+        let client = Client::with_http_client(backend.client(), None, None);
+        let results = client
+            .ohlc_many(&["BTCUSDT", "FOOBAR", "ETHUSDT"], 10..=20, 1)
+            .await;
+        let pairs = results.iter().map(|(pair, _)| pair).collect::<Vec<_>>();
+        assert_eq!(pairs, ["BTCUSDT", "FOOBAR", "ETHUSDT"]);
+        assert_eq!(results[0].1.as_ref().unwrap().len(), 1);
+        assert!(results[1].1.is_err());
+        assert!(results[2].1.as_ref().unwrap().is_empty());
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].url.query(), Some("from=10&pair=FOOBAR&to=21"));
+    }
+
+    #[async_std::test]
+    async fn send_resolution() {
+        let backend = MockHttpClient::new();
+        backend.push(surf::StatusCode::Ok, include_str!("klines_sample.json"));
+        let client = Client::with_http_client(backend.client(), None, None);
+        let ohlc = client
+            .ohlc_with_resolution("BTCUSDT", 10..20, OhlcResolution::OneHour)
+            .await
+            .unwrap();
+        assert_eq!(ohlc[0].pair, "BTCUSDT");
+        let url = &backend.requests()[0].url;
+        let query = url.query().unwrap_or_default();
+        assert!(query.contains("resolution=60"), "{url}");
+        assert!(query.contains("from=10"), "{url}");
+    }
 }
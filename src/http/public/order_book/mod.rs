@@ -1,16 +1,18 @@
 //! Implementation of the order book endpoint.
 
+use std::{cmp::Reverse, time::SystemTime};
+
 use surf::http::Method;
 
 use rust_decimal::Decimal;
-use serde::Deserialize;
+use serde::{de::Error as _, Deserialize, Deserializer};
 
 use crate::{
     error::{Parameter, SendRequest},
-    http::{request::Parameters, Client, Request},
+    http::{request::Parameters, Client, Data, OrderType, Request},
 };
 
-impl Client<'_> {
+impl Client {
     /// Get a list of all open orders for a product.
     ///
     /// In case of a system failure and delays in real time order book data,
@@ -19,8 +21,9 @@ impl Client<'_> {
     ///
     /// # Parameters
     /// - `pair_symbol`: For example, `BTCUSDT`.
-    /// - `limit`: Number of orders to get. Maximum is **1000**.
-    /// Defaults to **100**.
+    /// - `limit`: Number of orders to get. Must be between **1** and
+    ///   **1000**. Defaults to **100**. The limit is always sent so the
+    ///   result doesn't depend on the default of the server.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
     /// is an error or a malformation in the received response.
@@ -33,14 +36,11 @@ impl Client<'_> {
     ) -> Result<OrderBook, SendRequest> {
         let mut parameters = Parameters::new();
         parameters.push_string("pairSymbol", Some(pair_symbol.into()));
-        if let Some(limit) = limit {
-            if limit > 1000 {
-                return Err(
-                    Parameter::new("limit", limit.to_string()).into()
-                );
-            }
-            parameters.push_number("limit", Some(limit));
+        let limit = limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=1000).contains(&limit) {
+            return Err(Parameter::new("limit", limit.to_string()).into());
         }
+        parameters.push_number("limit", Some(limit));
         self.send(
             Request {
                 endpoint: self.url_cache().order_book(),
@@ -52,24 +52,251 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Estimate the slippage of a market order of `quantity` on the current
+    /// [`order_book`][Self::order_book] of the pair with
+    /// [`OrderBook::estimate_slippage`]. The default number of levels is
+    /// taken so the fill of a large order may be incomplete.
+    ///
+    /// Nothing is submitted. The book may change before an order is matched.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    /// # Parameters
+    /// - `pair_symbol`: For example, `BTCUSDT`.
+    /// - `side`: Side of the market order.
+    /// - `quantity`: Quantity of the market order.
+    pub async fn estimate_market_slippage(
+        &self,
+        pair_symbol: impl Into<String> + Send,
+        side: OrderType,
+        quantity: Decimal,
+    ) -> Result<SlippageEstimate, SendRequest> {
+        Ok(self
+            .order_book(pair_symbol, None)
+            .await?
+            .estimate_slippage(side, quantity))
+    }
 }
 
+/// Number of orders [`Client::order_book`] gets if no limit is given.
+const DEFAULT_LIMIT: u16 = 100;
+
+/// Largest difference between the best price and the price a market order
+/// is filled at, as a fraction of the best price. The exchange doesn't fill
+/// a market order beyond this band.
+///
+/// See the `price` parameter of [`Client::market_buy`].
+pub const MARKET_PRICE_BAND: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
 /// **Sample**:
 ///```json
 #[doc = include_str!("sample.json")]
 ///```
 ///See also <https://docs.btcturk.com/public-endpoints/orderbook>
-#[derive(serde::Deserialize, Debug, Clone, PartialEq, PartialOrd)]
+#[derive(
+    serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct OrderBook {
-    #[allow(missing_docs)]
-    pub timestamp: f64,
+    /// Milliseconds since the Unix epoch. The API sends a fractional value,
+    /// e.g. `1643883463379.0`, which is rounded to the nearest millisecond.
+    #[serde(deserialize_with = "millis_from_f64")]
+    pub timestamp: u64,
     #[allow(missing_docs)]
     pub bids: Vec<BidAsk>,
     #[allow(missing_docs)]
     pub asks: Vec<BidAsk>,
 }
 
+impl Data for OrderBook {}
+
+impl OrderBook {
+    /// Get the bid with the highest price. The order of the bids doesn't
+    /// matter.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<BidAsk> {
+        self.bids.iter().max_by_key(|bid| bid.price).copied()
+    }
+
+    /// Get the ask with the lowest price. The order of the asks doesn't
+    /// matter.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<BidAsk> {
+        self.asks.iter().min_by_key(|ask| ask.price).copied()
+    }
+
+    /// Get the difference between the best ask and the best bid prices.
+    /// Returns `None` if either side of the book is empty.
+    #[must_use]
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Sort the bids in descending and the asks in ascending order of price
+    /// so that the best levels come first.
+    pub fn sort(&mut self) {
+        self.bids.sort_by_key(|bid| Reverse(bid.price));
+        self.asks.sort_by_key(|ask| ask.price);
+    }
+
+    /// Get a copy of the book with the best `n` bids and the best `n` asks.
+    /// The levels are sorted as in [`sort`][Self::sort] first.
+    #[must_use]
+    pub fn top(&self, n: usize) -> Self {
+        let mut book = self.clone();
+        book.sort();
+        book.bids.truncate(n);
+        book.asks.truncate(n);
+        book
+    }
+
+    /// Estimate the result of a market order of `quantity` by consuming the
+    /// levels of the book from the best price on. A buy order consumes the
+    /// asks and a sell order consumes the bids. The order of the levels
+    /// doesn't matter.
+    #[must_use]
+    pub fn estimate_fill(
+        &self,
+        side: OrderType,
+        quantity: Decimal,
+    ) -> FillEstimate {
+        let mut fill = self.walk(side, |level, filled, _| {
+            let remaining = quantity - filled;
+            if remaining <= Decimal::ZERO {
+                return None;
+            }
+            let amount = level.amount.min(remaining);
+            Some((amount, amount * level.price))
+        });
+        fill.complete = fill.quantity >= quantity;
+        fill
+    }
+
+    /// Estimate the result of a market buy which spends `quote_amount` of the
+    /// quote currency, e.g. `USDT` for `BTCUSDT`, by consuming the asks from
+    /// the best price on. The order of the levels doesn't matter.
+    ///
+    /// The estimate is [`complete`][FillEstimate::complete] if the asks are
+    /// deep enough to spend the whole amount.
+    #[must_use]
+    pub fn estimate_buy_with(&self, quote_amount: Decimal) -> FillEstimate {
+        let mut fill = self.walk(OrderType::Buy, |ask, _, total| {
+            let remaining = quote_amount - total;
+            if remaining <= Decimal::ZERO || ask.price <= Decimal::ZERO {
+                return None;
+            }
+            let cost = (ask.amount * ask.price).min(remaining);
+            Some((cost / ask.price, cost))
+        });
+        fill.complete = fill.total >= quote_amount;
+        fill
+    }
+
+    /// Consume the levels of the side which an order of `side` fills against
+    /// from the best price on. `take` gets a level with the quantity and the
+    /// total filled so far and returns the quantity and the total to take
+    /// from the level, or `None` to stop.
+    fn walk<F>(&self, side: OrderType, mut take: F) -> FillEstimate
+    where
+        F: FnMut(BidAsk, Decimal, Decimal) -> Option<(Decimal, Decimal)>,
+    {
+        let mut book = self.clone();
+        book.sort();
+        let levels = match side {
+            OrderType::Buy => book.asks,
+            OrderType::Sell => book.bids,
+        };
+        let mut filled = Decimal::ZERO;
+        let mut total = Decimal::ZERO;
+        let mut last_price = None;
+        for level in levels {
+            let Some((amount, cost)) = take(level, filled, total) else {
+                break;
+            };
+            filled += amount;
+            total += cost;
+            last_price = Some(level.price);
+        }
+        FillEstimate {
+            quantity: filled,
+            total,
+            average_price: total.checked_div(filled),
+            last_price,
+            complete: false,
+        }
+    }
+
+    /// Estimate the slippage of a market order of `quantity` with
+    /// [`estimate_fill`][Self::estimate_fill], i.e. how much worse the
+    /// average price is than the best price of the consumed side.
+    ///
+    /// The estimate [`exceeds_band`][SlippageEstimate::exceeds_band] if the
+    /// order would reach a price beyond the [`MARKET_PRICE_BAND`].
+    #[must_use]
+    pub fn estimate_slippage(
+        &self,
+        side: OrderType,
+        quantity: Decimal,
+    ) -> SlippageEstimate {
+        let fill = self.estimate_fill(side, quantity);
+        let best_price = match side {
+            OrderType::Buy => self.best_ask(),
+            OrderType::Sell => self.best_bid(),
+        }
+        .map(|level| level.price);
+        // Positive if the price is worse than the best price.
+        let deviation = |price: Decimal| {
+            let best_price = best_price?;
+            let difference = match side {
+                OrderType::Buy => price - best_price,
+                OrderType::Sell => best_price - price,
+            };
+            difference.checked_div(best_price)
+        };
+        SlippageEstimate {
+            fill,
+            best_price,
+            slippage: fill.average_price.and_then(deviation),
+            exceeds_band: fill
+                .last_price
+                .and_then(deviation)
+                .is_some_and(|deviation| deviation > MARKET_PRICE_BAND),
+        }
+    }
+}
+
+impl OrderBook {
+    /// Get [`timestamp`][Self::timestamp] as a system time.
+    #[must_use]
+    pub fn timestamp_as_system_time(&self) -> SystemTime {
+        crate::system_time::from_millis(self.timestamp)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl OrderBook {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
+/// Round the fractional milliseconds sent by the API to the nearest
+/// millisecond.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn millis_from_f64<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    let millis = f64::deserialize(deserializer)?;
+    if !millis.is_finite() || millis < 0.0 {
+        return Err(D::Error::custom(format!("invalid timestamp: {millis}")));
+    }
+    Ok(millis.round() as u64)
+}
+
 /// **Sample**:
 /// ```json
 /// [
@@ -101,11 +328,61 @@ impl From<BidAskRaw> for BidAsk {
 #[derive(serde::Deserialize)]
 struct BidAskRaw(Decimal, Decimal);
 
+/// Result of [`OrderBook::estimate_fill`] and
+/// [`OrderBook::estimate_buy_with`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FillEstimate {
+    /// Quantity which the book can fill. Less than the requested quantity if
+    /// the book is not deep enough.
+    pub quantity: Decimal,
+    /// Cost of a buy order or proceeds of a sell order in the quote currency,
+    /// e.g. `USDT` for `BTCUSDT`.
+    pub total: Decimal,
+    /// Average price of the filled [`quantity`][Self::quantity]. `None` if
+    /// nothing can be filled.
+    pub average_price: Option<Decimal>,
+    /// Price of the last level the fill reaches, i.e. the worst price.
+    /// `None` if nothing can be filled.
+    pub last_price: Option<Decimal>,
+    /// Whether the book is deep enough to fill the whole requested quantity
+    /// or to spend the whole requested amount.
+    pub complete: bool,
+}
+
+/// Result of [`OrderBook::estimate_slippage`] and
+/// [`Client::estimate_market_slippage`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SlippageEstimate {
+    /// Estimated fill of the order.
+    pub fill: FillEstimate,
+    /// Price of the best ask for a buy order or the best bid for a sell
+    /// order. `None` if that side of the book is empty.
+    pub best_price: Option<Decimal>,
+    /// Difference between the [`average_price`][FillEstimate::average_price]
+    /// and the [`best_price`][Self::best_price] as a fraction of the best
+    /// price, e.g. `0.01` for 1%. Positive if the average price is worse.
+    /// `None` if nothing can be filled.
+    pub slippage: Option<Decimal>,
+    /// Whether the [`last_price`][FillEstimate::last_price] is farther than
+    /// the [`MARKET_PRICE_BAND`] from the best price. The exchange won't
+    /// fill such an order completely.
+    pub exceeds_band: bool,
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use pretty_assertions::assert_eq;
+    use rust_decimal_macros::dec;
+
+    use super::{BidAsk, FillEstimate, MARKET_PRICE_BAND};
     use crate::{
         error::SendRequest,
-        http::{public::order_book::OrderBook, Client},
+        http::{
+            mock::MockHttpClient, public::order_book::OrderBook, Client,
+            OrderType,
+        },
     };
 
     #[ignore]
@@ -151,4 +428,177 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<OrderBook>(json_string).unwrap();
     }
+
+    #[test]
+    fn round_fractional_timestamp() {
+        let order_book = |timestamp| {
+            serde_json::from_str::<OrderBook>(&format!(
+                r#"{{"timestamp": {timestamp}, "bids": [], "asks": []}}"#
+            ))
+        };
+        assert_eq!(
+            order_book("1643883463379.4").unwrap().timestamp,
+            1_643_883_463_379
+        );
+        assert_eq!(
+            order_book("1643883463379.5").unwrap().timestamp,
+            1_643_883_463_380
+        );
+        assert_eq!(
+            order_book("1643883463379").unwrap().timestamp,
+            1_643_883_463_379
+        );
+        assert!(order_book("-1.0").is_err());
+    }
+
+    #[test]
+    fn convert_timestamp_to_system_time() {
+        let json_string = include_str!("sample.json");
+        let order_book =
+            serde_json::from_str::<OrderBook>(json_string).unwrap();
+        assert_eq!(
+            order_book.timestamp_as_system_time(),
+            UNIX_EPOCH + Duration::from_millis(1_643_883_463_379)
+        );
+    }
+
+    #[test]
+    fn best_levels_and_spread() {
+        let json_string = include_str!("sample.json");
+        let mut order_book =
+            serde_json::from_str::<OrderBook>(json_string).unwrap();
+        order_book.bids.reverse();
+        order_book.asks.reverse();
+        let best_bid = order_book.best_bid().unwrap();
+        let best_ask = order_book.best_ask().unwrap();
+        assert_eq!(best_bid.price, dec!(36371));
+        assert_eq!(best_ask.price, dec!(36401));
+        assert_eq!(order_book.spread(), Some(dec!(30)));
+
+        order_book.sort();
+        assert_eq!(order_book.bids[0], best_bid);
+        assert_eq!(order_book.asks[0], best_ask);
+
+        order_book.asks.clear();
+        assert_eq!(order_book.spread(), None);
+    }
+
+    #[test]
+    fn take_top_levels() {
+        let json_string = include_str!("sample.json");
+        let mut order_book =
+            serde_json::from_str::<OrderBook>(json_string).unwrap();
+        order_book.bids.reverse();
+        order_book.asks.reverse();
+        let top = order_book.top(1);
+        assert_eq!(top.bids, vec![order_book.best_bid().unwrap()]);
+        assert_eq!(top.asks, vec![order_book.best_ask().unwrap()]);
+        assert_eq!(
+            top.timestamp_as_system_time(),
+            order_book.timestamp_as_system_time()
+        );
+        assert_eq!(order_book.top(5).bids.len(), 2);
+    }
+
+    #[async_std::test]
+    async fn normalize_limit() {
+        let backend = MockHttpClient::new();
+        for _ in 0..2 {
+            backend.push_data(include_str!("sample.json"));
+        }
+        let client = Client::with_http_client(backend.client(), None, None);
+        client.order_book("BTCTRY", None).await.unwrap();
+        client.order_book("BTCTRY", Some(5)).await.unwrap();
+        let error = client.order_book("BTCTRY", Some(0)).await.unwrap_err();
+        assert!(matches!(error, SendRequest::ParameterError { .. }));
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].url.query(),
+            Some("limit=100&pairSymbol=BTCTRY")
+        );
+        assert_eq!(requests[1].url.query(), Some("limit=5&pairSymbol=BTCTRY"));
+    }
+
+    #[test]
+    fn estimate_buy_with_quote_amount() {
+        let json_string = include_str!("sample.json");
+        let order_book =
+            serde_json::from_str::<OrderBook>(json_string).unwrap();
+        let estimate = order_book.estimate_buy_with(dec!(36.401));
+        assert_eq!(estimate.quantity, dec!(0.001));
+        assert_eq!(estimate.average_price, Some(dec!(36401)));
+        assert!(estimate.complete);
+
+        let estimate = order_book.estimate_buy_with(dec!(1000));
+        assert_eq!(estimate.total, dec!(235.44989630));
+        assert_eq!(estimate.quantity, dec!(0.00646815));
+        assert!(!estimate.complete);
+    }
+
+    #[test]
+    fn estimate_fill() {
+        let json_string = include_str!("sample.json");
+        let order_book =
+            serde_json::from_str::<OrderBook>(json_string).unwrap();
+        assert_eq!(
+            order_book.estimate_fill(OrderType::Buy, dec!(0.005)),
+            FillEstimate {
+                quantity: dec!(0.005),
+                total: dec!(182.0063),
+                average_price: Some(dec!(36401.26)),
+                last_price: Some(dec!(36402)),
+                complete: true,
+            }
+        );
+        let estimate = order_book.estimate_fill(OrderType::Sell, dec!(1));
+        assert_eq!(estimate.quantity, dec!(0.24133385));
+        assert_eq!(estimate.total, dec!(8777.3129245));
+        assert!(!estimate.complete);
+
+        let estimate = order_book.estimate_fill(OrderType::Sell, dec!(0));
+        assert_eq!(estimate.average_price, None);
+        assert!(estimate.complete);
+    }
+
+    #[async_std::test]
+    async fn estimate_slippage() {
+        let level = |price, amount| BidAsk { price, amount };
+        let order_book = OrderBook {
+            timestamp: 0,
+            bids: vec![level(dec!(90), dec!(1)), level(dec!(100), dec!(1))],
+            asks: vec![
+                level(dec!(104), dec!(1)),
+                level(dec!(100), dec!(1)),
+                level(dec!(106), dec!(1)),
+            ],
+        };
+        let estimate = order_book.estimate_slippage(OrderType::Buy, dec!(2));
+        assert_eq!(estimate.best_price, Some(dec!(100)));
+        assert_eq!(estimate.slippage, Some(dec!(0.02)));
+        assert!(!estimate.exceeds_band);
+        assert!(
+            order_book
+                .estimate_slippage(OrderType::Buy, dec!(3))
+                .exceeds_band
+        );
+        let estimate = order_book.estimate_slippage(OrderType::Sell, dec!(2));
+        assert_eq!(estimate.slippage, Some(MARKET_PRICE_BAND));
+        assert_eq!(estimate.fill.last_price, Some(dec!(90)));
+        assert!(estimate.exceeds_band);
+
+        let backend = MockHttpClient::new();
+        backend.push_data(include_str!("sample.json"));
+        let client = Client::with_http_client(backend.client(), None, None);
+        let estimate = client
+            .estimate_market_slippage("BTCTRY", OrderType::Buy, dec!(0.001))
+            .await
+            .unwrap();
+        assert_eq!(estimate.slippage, Some(dec!(0)));
+        assert!(!estimate.exceeds_band);
+        assert_eq!(
+            backend.requests()[0].url.query(),
+            Some("limit=100&pairSymbol=BTCTRY")
+        );
+    }
 }
@@ -4,16 +4,16 @@ use surf::http::Method;
 
 use crate::{
     error::{Response as ResponseError, SendRequest},
-    http::{request::Parameters, Client, Request},
+    http::{request::Parameters, Client, Request, Single},
 };
 
 use rust_decimal::Decimal;
 
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 /// Available currencies in the exchange to be used with
 /// the [`currency`][Client::currency] method.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Currency {
     /// Tether
     Usdt,
@@ -21,14 +21,18 @@ pub enum Currency {
     Try,
     /// Bitcoin
     Btc,
+    /// Any other currency symbol, e.g. a newly listed quote currency. The
+    /// symbol is sent as is.
+    Other(String),
 }
 
 impl Display for Currency {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
-            Currency::Usdt => "USDT",
-            Currency::Try => "TRY",
-            Currency::Btc => "BTC",
+            Self::Usdt => "USDT",
+            Self::Try => "TRY",
+            Self::Btc => "BTC",
+            Self::Other(symbol) => symbol,
         })
     }
 }
@@ -39,7 +43,7 @@ impl From<Currency> for String {
     }
 }
 
-impl Client<'_> {
+impl Client {
     /// Gets snapshot information about the last trade (tick), best bid/ask and
     /// 24h volume. \
     /// Using the `pair_symbol` parameter, you can send a request for a single
@@ -52,16 +56,19 @@ impl Client<'_> {
     /// - `pair_symbol`: For example, `BTCUSDT`.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
-    /// is an error or a malformation in the received response.
+    /// is an error or a malformation in the received response. If the pair
+    /// is not listed, the error is a
+    /// [`PairNotFound`][ResponseError::PairNotFound] response error.
     ///
     /// See also <https://docs.btcturk.com/public-endpoints/ticker>.
     pub async fn ticker(
         &self,
         pair_symbol: impl Into<String> + Send,
     ) -> Result<Ticker, SendRequest> {
+        let pair_symbol = pair_symbol.into();
         let mut parameters = Parameters::new();
-        parameters.push_string("pairSymbol", Some(pair_symbol.into()));
-        self.send::<Vec<Ticker>>(
+        parameters.push_string("pairSymbol", Some(pair_symbol.clone()));
+        self.send::<Single<Ticker>>(
             Request {
                 endpoint: self.url_cache().ticker(),
                 method: Method::Get,
@@ -71,10 +78,9 @@ impl Client<'_> {
             false,
         )
         .await?
-        .into_iter()
-        .next()
+        .into_inner()
         .ok_or(SendRequest::ResponseError {
-            source: ResponseError::EmptyData,
+            source: ResponseError::PairNotFound { pair: pair_symbol },
         })
     }
 
@@ -98,12 +104,29 @@ impl Client<'_> {
         .await
     }
 
+    /// Same as [`tickers`][Self::tickers] but the tickers are keyed by their
+    /// [`pair`][Ticker::pair], e.g. `BTCUSDT`, for quick lookups.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response.
+    pub async fn tickers_map(
+        &self,
+    ) -> Result<HashMap<String, Ticker>, SendRequest> {
+        Ok(self
+            .tickers()
+            .await?
+            .into_iter()
+            .map(|ticker| (ticker.pair.clone(), ticker))
+            .collect())
+    }
+
     /// Same as [`ticker`][Self::ticker] but accepts a currency
     /// instead of a symbol pair and returns tickers of the symbols paired with
     /// that currency.
     ///
     /// # Parameters
-    /// - `symbol`: Can be one of `USDT`, `TRY`, or `BTC`.
+    /// - `symbol`: For example, `USDT`, `TRY`, or `BTC`. Use
+    ///   [`Currency::Other`] for the others.
     /// # Errors
     /// [`SendRequest`] if there is an error sending the request or there
     /// is an error or a malformation in the received response.
@@ -126,6 +149,25 @@ impl Client<'_> {
         )
         .await
     }
+
+    /// Same as [`currency`][Self::currency] but the symbol is sent as is.
+    /// Use it for the currencies which are not in [`Currency`], e.g. a newly
+    /// listed quote currency.
+    ///
+    /// # Parameters
+    /// - `symbol`: For example, `EUR`.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the request or there
+    /// is an error or a malformation in the received response. The server
+    /// responds with an error if the symbol is not valid.
+    ///
+    /// See also <https://docs.btcturk.com/public-endpoints/ticker#get-currency>.
+    pub async fn currency_by_symbol(
+        &self,
+        symbol: impl Into<String> + Send,
+    ) -> Result<Vec<Ticker>, SendRequest> {
+        self.currency(Currency::Other(symbol.into())).await
+    }
 }
 
 /// **Sample**:
@@ -137,6 +179,7 @@ impl Client<'_> {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Ticker {
     #[allow(missing_docs)]
     pub pair: String,
@@ -168,17 +211,49 @@ pub struct Ticker {
     pub denominator_symbol: String,
     #[allow(missing_docs)]
     pub numerator_symbol: String,
-    #[allow(missing_docs)]
+    /// Position the exchange lists the pair at. See
+    /// [`sort_by_display_order`][crate::http::sort_by_display_order].
     pub order: u64,
 }
 
+impl Ticker {
+    /// Get the price halfway between the [`bid`][Self::bid] and the
+    /// [`ask`][Self::ask].
+    #[must_use]
+    pub fn mid_price(&self) -> Decimal {
+        (self.bid + self.ask) / Decimal::TWO
+    }
+
+    /// Get the difference between the [`ask`][Self::ask] and the
+    /// [`bid`][Self::bid].
+    #[must_use]
+    pub fn spread(&self) -> Decimal {
+        self.ask - self.bid
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Ticker {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::{assert_eq, assert_str_eq};
     use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
 
-    use crate::http::{
-        public::ticker::{Currency, Ticker},
-        Client,
+    use crate::{
+        error::{Response as ResponseError, SendRequest},
+        http::{
+            mock::MockHttpClient,
+            public::ticker::{Currency, Ticker},
+            Client,
+        },
     };
 
     #[ignore]
@@ -195,6 +270,21 @@ mod tests {
         assert!(last > Decimal::ZERO);
     }
 
+    #[async_std::test]
+    async fn ticker_of_unknown_pair() {
+        let backend = MockHttpClient::new();
+        backend.push_data("[]");
+        let client = Client::with_http_client(backend.client(), None, None);
+        let error = client.ticker("BTCXYZ").await.unwrap_err();
+        let SendRequest::ResponseError {
+            source: ResponseError::PairNotFound { pair },
+        } = error
+        else {
+            panic!("unexpected error: `{error}`");
+        };
+        assert_str_eq!(pair, "BTCXYZ");
+    }
+
     #[ignore]
     #[async_std::test]
     async fn get_tickers() {
@@ -233,4 +323,38 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<Ticker>(json_string).unwrap();
     }
+
+    #[async_std::test]
+    async fn key_tickers_by_pair() {
+        let backend = MockHttpClient::new();
+        backend.push_data(&format!("[{}]", include_str!("sample.json")));
+        let client = Client::with_http_client(backend.client(), None, None);
+        let tickers = client.tickers_map().await.unwrap();
+        assert_eq!(tickers.len(), 1);
+        assert_str_eq!(tickers["BTCUSDT"].pair_normalized, "BTC_USDT");
+    }
+
+    #[test]
+    fn mid_price_and_spread() {
+        let json_string = include_str!("sample.json");
+        let ticker = serde_json::from_str::<Ticker>(json_string).unwrap();
+        assert_eq!(ticker.mid_price(), dec!(36435.5));
+        assert_eq!(ticker.spread(), dec!(61));
+    }
+
+    #[async_std::test]
+    async fn get_currency_by_symbol() {
+        let backend = MockHttpClient::new();
+        backend.push_data(&format!("[{}]", include_str!("sample.json")));
+        let client = Client::with_http_client(backend.client(), None, None);
+        let tickers = client.currency_by_symbol("EUR").await.unwrap();
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(backend.requests()[0].url.query(), Some("symbol=EUR"));
+    }
+
+    #[test]
+    fn currency_to_string() {
+        assert_str_eq!(String::from(Currency::Try), "TRY");
+        assert_str_eq!(String::from(Currency::Other("EUR".into())), "EUR");
+    }
 }
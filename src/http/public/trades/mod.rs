@@ -9,7 +9,7 @@ use crate::{
     Client,
 };
 
-impl Client<'_> {
+impl Client {
     /// Gets a list the latest trades for a product.
     ///
     /// # Parameters
@@ -58,12 +58,15 @@ impl Client<'_> {
     serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
 )]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Trade {
     #[allow(missing_docs)]
     pub pair: String,
     #[allow(missing_docs)]
     pub pair_normalized: String,
     #[allow(missing_docs)]
+    pub numerator: String,
+    #[allow(missing_docs)]
     pub denominator: String,
     #[allow(missing_docs)]
     pub date: u64,
@@ -78,6 +81,15 @@ pub struct Trade {
     pub side: OrderType,
 }
 
+#[cfg(feature = "chrono")]
+impl Trade {
+    /// Get [`date`][Self::date] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.date)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Client;
@@ -105,4 +117,17 @@ mod tests {
         let json_string = include_str!("sample.json");
         serde_json::from_str::<Vec<Trade>>(json_string).unwrap();
     }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn deny_unknown_fields() {
+        let json_string = include_str!("sample.json").replacen(
+            r#""side""#,
+            r#""new": 1, "side""#,
+            1,
+        );
+        let error =
+            serde_json::from_str::<Vec<Trade>>(&json_string).unwrap_err();
+        assert!(error.to_string().contains("unknown field `new`"));
+    }
 }
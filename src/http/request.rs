@@ -1,9 +1,13 @@
 //! General request implementation.
 
+use std::ops::{Bound, RangeBounds};
+
 use rust_decimal::Decimal;
 use serde_json::{Map, Number, Value};
 use surf::{http::Method, Url};
 
+use crate::error::Parameter;
+
 #[derive(Debug, Clone)]
 pub struct Request<'a> {
     pub endpoint: &'a Url,
@@ -34,6 +38,21 @@ impl Parameters {
         &self.json_root
     }
 
+    /// Render the parameters as the pairs of a query string. Strings are not
+    /// quoted and numbers and booleans are written as they are. An array is
+    /// rendered as a pair for each of its items.
+    pub fn to_query_pairs(&self) -> impl Iterator<Item = (&str, String)> {
+        self.json_root.iter().flat_map(|(name, value)| {
+            let values = match value {
+                Value::Array(values) => values.iter().map(render).collect(),
+                value => vec![render(value)],
+            };
+            values.into_iter().map(move |value| (name.as_str(), value))
+        })
+    }
+
+    /// Push the decimal as a JSON string, e.g. `"0.01"`. The endpoints
+    /// which take decimals so far expect strings.
     pub fn push_decimal(
         &mut self,
         name: impl Into<String>,
@@ -52,6 +71,20 @@ impl Parameters {
         }
     }
 
+    /// Push the strings as a JSON array. Nothing is pushed if there are no
+    /// strings. In a query string, the parameter is repeated for each string,
+    /// e.g. `symbol=btc&symbol=try`.
+    pub fn push_strings(
+        &mut self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = String>,
+    ) {
+        let values = values.into_iter().map(Value::String).collect::<Vec<_>>();
+        if !values.is_empty() {
+            self.json_root.insert(name.into(), Value::Array(values));
+        }
+    }
+
     pub fn push_object(
         &mut self,
         name: impl Into<String>,
@@ -63,6 +96,16 @@ impl Parameters {
         }
     }
 
+    /// Push the boolean as a JSON boolean. In a query string, it is
+    /// `true` or `false`.
+    // Kept for the endpoints which take a boolean parameter.
+    #[allow(dead_code)]
+    pub fn push_bool(&mut self, name: impl Into<String>, value: Option<bool>) {
+        if let Some(value) = value {
+            self.json_root.insert(name.into(), Value::Bool(value));
+        }
+    }
+
     pub fn push_number<D>(&mut self, name: impl Into<String>, value: Option<D>)
     where
         serde_json::Number: From<D>,
@@ -72,4 +115,138 @@ impl Parameters {
                 .insert(name.into(), Value::Number(Number::from(value)));
         }
     }
+
+    /// Push the start and the end of the range with the given names. The
+    /// bounds are sent as a half-open range like a [`Range`][std::ops::Range],
+    /// so an excluded start and an included end are sent as the next number.
+    /// An unbounded side is left out. An inverted range is rejected with an
+    /// error named after the range parameter instead of silently returning
+    /// nothing.
+    pub fn push_range(
+        &mut self,
+        name: &'static str,
+        (start_name, end_name): (&str, &str),
+        range: impl RangeBounds<u64>,
+    ) -> Result<(), Parameter> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => Some(*start),
+            Bound::Excluded(start) => Some(start.saturating_add(1)),
+            Bound::Unbounded => None,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => Some(end.saturating_add(1)),
+            Bound::Excluded(end) => Some(*end),
+            Bound::Unbounded => None,
+        };
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(Parameter::new(name, format!("{start}..{end}")));
+            }
+        }
+        self.push_number(start_name, start);
+        self.push_number(end_name, end);
+        Ok(())
+    }
+}
+
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    use rust_decimal_macros::dec;
+
+    use super::Parameters;
+
+    #[test]
+    fn push_bool() {
+        let mut parameters = Parameters::new();
+        parameters.push_bool("flag", Some(true));
+        parameters.push_bool("missing", None);
+        assert_eq!(parameters.root().get("flag"), Some(&Value::Bool(true)));
+        assert_eq!(
+            serde_json::to_string(parameters.root()).unwrap(),
+            r#"{"flag":true}"#
+        );
+    }
+
+    #[test]
+    fn push_range_bounds() {
+        use std::ops::Bound;
+
+        let mut parameters = Parameters::new();
+        parameters
+            .push_range("range", ("from", "to"), 5..=9)
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(parameters.root()).unwrap(),
+            r#"{"from":5,"to":10}"#
+        );
+        let mut parameters = Parameters::new();
+        parameters
+            .push_range(
+                "range",
+                ("from", "to"),
+                (Bound::Excluded(5), Bound::Unbounded),
+            )
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(parameters.root()).unwrap(),
+            r#"{"from":6}"#
+        );
+        let mut parameters = Parameters::new();
+        parameters.push_range("range", ("from", "to"), ..).unwrap();
+        assert_eq!(serde_json::to_string(parameters.root()).unwrap(), "{}");
+        let mut parameters = Parameters::new();
+        let inverted = (Bound::Included(9), Bound::Excluded(5));
+        assert!(parameters
+            .push_range("range", ("from", "to"), inverted)
+            .is_err());
+    }
+
+    #[test]
+    fn serialize_decimals() {
+        let mut parameters = Parameters::new();
+        parameters.push_decimal("price", Some(dec!(0.01)));
+        parameters.push_decimal("missing", None);
+        assert_eq!(
+            serde_json::to_string(parameters.root()).unwrap(),
+            r#"{"price":"0.01"}"#
+        );
+    }
+
+    #[test]
+    fn render_query_pairs_unquoted() {
+        let mut parameters = Parameters::new();
+        parameters.push_string("pairSymbol", Some("BTCTRY".to_owned()));
+        parameters.push_number("limit", Some(100));
+        parameters.push_decimal("price", Some(dec!(0.01)));
+        parameters.push_bool("flag", Some(false));
+        parameters.push_strings("symbol", ["btc".to_owned(), "try".to_owned()]);
+        parameters.push_strings("missing", []);
+        let pairs = parameters
+            .to_query_pairs()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [
+                "flag=false",
+                "limit=100",
+                "pairSymbol=BTCTRY",
+                "price=0.01",
+                "symbol=btc",
+                "symbol=try"
+            ]
+        );
+    }
 }
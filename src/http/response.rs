@@ -1,7 +1,14 @@
 //! General response implementation.
 
+use std::{fmt, marker::PhantomData};
+
 use crate::error::Response as ResponseError;
-use serde::Deserialize;
+use serde::{
+    de::{
+        value::MapAccessDeserializer, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    },
+    Deserialize, Deserializer,
+};
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Response<D> {
@@ -11,17 +18,69 @@ pub struct Response<D> {
     code: i64,
 }
 
-impl<D> Response<D> {
-    pub fn data(self) -> Result<D, ResponseError> {
+/// Data of a successful response. Some endpoints send a null or no data
+/// instead of an empty list, so [`missing`][Self::missing] tells what such a
+/// response stands for.
+pub trait Data: Sized {
+    /// Value of a null or missing data. `None`, the default, makes it a
+    /// [`NullData`][ResponseError::NullData] error.
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+/// A null or missing list is empty.
+impl<T> Data for Vec<T> {
+    fn missing() -> Option<Self> {
+        Some(Self::new())
+    }
+}
+
+/// A null or missing item is `None`.
+impl<T> Data for Single<T> {
+    fn missing() -> Option<Self> {
+        Some(Self(None))
+    }
+}
+
+impl<D: Data> Response<D> {
+    /// Get the data of a successful response. The given JSON string of the
+    /// response is attached to the error if the response is unsuccessful.
+    ///
+    /// A null or missing data is replaced by [`Data::missing`], e.g. an
+    /// empty list.
+    pub fn data(self, response_string: &str) -> Result<D, ResponseError> {
         if !self.success {
             Err(ResponseError::Unsuccessful {
                 code: self.code,
                 message: self.message,
+                response_string: response_string.to_owned(),
             })
         } else if let Some(data) = self.data {
             Ok(data)
         } else {
-            Err(ResponseError::NullData)
+            D::missing().ok_or(ResponseError::NullData)
+        }
+    }
+}
+
+impl<D> Response<D> {
+    /// Get the code and the message of a successful response. Unlike
+    /// [`data`][Self::data], a missing data is not an error. The given JSON
+    /// string of the response is attached to the error if the response is
+    /// unsuccessful.
+    pub fn status(
+        self,
+        response_string: &str,
+    ) -> Result<(i64, Option<String>), ResponseError> {
+        if self.success {
+            Ok((self.code, self.message))
+        } else {
+            Err(ResponseError::Unsuccessful {
+                code: self.code,
+                message: self.message,
+                response_string: response_string.to_owned(),
+            })
         }
     }
 
@@ -36,11 +95,68 @@ impl<D> Response<D> {
     }
 }
 
+/// Data of an endpoint which returns a single item, whether the item is
+/// sent by itself or in a list, e.g. the ticker of a pair. A list is
+/// deserialized to its first item and an empty list to `None`. Send a
+/// request with it instead of unwrapping a list at each endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Single<T>(Option<T>);
+
+impl<T> Single<T> {
+    /// Get the item, if any.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Single<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct SingleVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for SingleVisitor<T> {
+            type Value = Single<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object or a list of objects")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let item = seq.next_element()?;
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(Single(item))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<Self::Value, A::Error> {
+                T::deserialize(MapAccessDeserializer::new(map))
+                    .map(|item| Single(Some(item)))
+            }
+        }
+
+        deserializer.deserialize_any(SingleVisitor(PhantomData))
+    }
+}
+
+/// The API sends an empty string instead of `null` for the missing values.
+pub fn empty_as_none<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?
+        .filter(|string| !string.is_empty()))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Response;
+    use super::{Response, Single};
     use crate::error::Response as ResponseError;
-    use crate::http::public::ticker::Ticker;
+    use crate::http::public::{order_book::OrderBook, ticker::Ticker};
     use pretty_assertions::assert_eq;
 
     fn get_ticker_data(json_string: &str) -> Response<Vec<Ticker>> {
@@ -80,7 +196,7 @@ mod tests {
         assert!(response.message.is_none());
         assert_eq!(response.code, 0);
         assert_eq!(response.success, true);
-        response.data().unwrap();
+        response.data(json_string).unwrap();
     }
 
     #[test]
@@ -95,12 +211,13 @@ mod tests {
     "###;
         let response = get_ticker_data(json_string);
         assert_eq!(
-            response.data(),
+            response.data(json_string),
             Err(ResponseError::Unsuccessful {
                 code: 1037,
                 message: Some(
                     "currencySymbol parameter must be set".to_string()
-                )
+                ),
+                response_string: json_string.to_string(),
             })
         );
     }
@@ -116,6 +233,33 @@ mod tests {
     }
     "###;
         let response = get_ticker_data(json_string);
-        assert_eq!(response.data(), Err(ResponseError::NullData));
+        assert_eq!(response.data(json_string), Ok(Vec::new()));
+
+        let response =
+            serde_json::from_str::<Response<OrderBook>>(json_string).unwrap();
+        assert_eq!(response.data(json_string), Err(ResponseError::NullData));
+
+        let json_string = r#"{"success": true, "message": null, "code": 0}"#;
+        let response = get_ticker_data(json_string);
+        assert_eq!(response.data(json_string), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn single_data() {
+        let ticker = include_str!("public/ticker/sample.json");
+        let single = |json_string: &str| {
+            let json_string = format!(
+                r#"{{"data": {json_string}, "success": true, "code": 0}}"#
+            );
+            serde_json::from_str::<Response<Single<Ticker>>>(&json_string)
+                .unwrap()
+                .data(&json_string)
+                .unwrap()
+                .into_inner()
+        };
+        let item = single(ticker).unwrap();
+        assert_eq!(single(&format!("[{ticker}, {ticker}]")), Some(item));
+        assert_eq!(single("[]"), None);
+        assert_eq!(single("null"), None);
     }
 }
@@ -7,14 +7,35 @@
 //! Unofficial [`BtcTurk` exchange](https://www.btcturk.com/) API bindings.
 //!
 //! Use this crate to make API calls to [`public`][crate::http::public] and
-//! [`private`][crate::http::private] endpoints. Websocket feed is not
-//! implemented yet. This is an async crate and blocking calls are
-//! not supported yet.
+//! [`private`][crate::http::private] endpoints and to subscribe to the
+//! [`websocket`] feed. This is an async crate but blocking versions of the
+//! HTTP API calls are available in the `blocking` module when the `blocking`
+//! feature is enabled.
 //!
 //! This crate was made with the help of the following documents:
 //! - <https://docs.btcturk.com/>
 //! - <https://github.com/BTCTrader/broker-api-docs/blob/master/README-pro.md>
 //!
+//! # Features
+//! - `async-std-runtime` (default): Connects to the websocket feed with
+//!   `async-std`.
+//! - `tokio-runtime`: Connects to the websocket feed with `tokio`. Disable
+//!   the default features to use it. Only one of the runtime features can be
+//!   enabled. They only select how the websocket connection and its TLS are
+//!   driven. The HTTP client and the `blocking` module don't depend on a
+//!   runtime and are the same under both.
+//! - `blocking`: Adds the `blocking` module which has a client that doesn't
+//!   need an async runtime.
+//! - `chrono`: Adds methods like `Ticker::datetime` which convert the raw
+//!   timestamps to `chrono::DateTime<Utc>`.
+//! - `tracing`: Runs each HTTP request in a `tracing` span which records the
+//!   endpoint, the method, the HTTP status and the duration.
+//! - `csv`: Adds `Client::export_trade_transactions_csv` which writes the
+//!   trade history as CSV.
+//! - `strict`: Fails the deserialization of the HTTP responses which have
+//!   fields unknown to this crate instead of ignoring them. Use it in tests to
+//!   notice the changes in the API early.
+//!
 //! # Examples
 //! ## Get a ticker
 //! ```no_run
@@ -39,6 +60,25 @@
 //!     Ok(())
 //! }
 //! ```
+//! ## Use with tokio
+//! HTTP requests don't depend on a particular runtime so [`Client`] works
+//! under `tokio` as well. Enable the `tokio-runtime` feature to use the
+//! [`websocket`] feed under `tokio` too.
+//! ```no_run
+//! # #[cfg(feature = "tokio-runtime")]
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = btcturk::Client::new(None, None)?;
+//!
+//!     let ticker = client.ticker("BTCTRY").await?;
+//!
+//!     println!("Last price of BTCTRY pair is {}", ticker.last);
+//!
+//!     Ok(())
+//! }
+//! # #[cfg(not(feature = "tokio-runtime"))]
+//! # fn main() {}
+//! ```
 //! ## Submit and cancel an order
 //! ```no_run
 //! # #[async_std::main]
@@ -93,6 +133,9 @@
 //! Beware that if you use the normal endpoint, tests will submit/cancel
 //! **real** orders.
 //!
+//! Outside of the tests, use [`Client::with_base_url`] to point a client to
+//! the testing endpoint or to a mock server.
+//!
 //! [page]: https://github.com/BTCTrader/broker-api-docs/blob/master/README-pro.md#testing
 //! ## Logging
 //! Logging is supported in the tests. Pass `RUST_LOG` environment variable
@@ -120,13 +163,33 @@
 //! $ KEYS_PATH=~/keys.txt cargo test get_all_orders -- --ignored
 //! ```
 
+#[cfg(not(any(feature = "async-std-runtime", feature = "tokio-runtime")))]
+compile_error!(
+    "enable either the `async-std-runtime` or the `tokio-runtime` feature"
+);
+
+#[cfg(all(feature = "async-std-runtime", feature = "tokio-runtime"))]
+compile_error!(
+    "enable only one of the `async-std-runtime` and the `tokio-runtime` \
+    features"
+);
+
 pub mod http;
 pub use http::ApiKeys;
 pub use http::Client;
 
 pub mod websocket;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "chrono")]
+mod datetime;
+mod system_time;
+
 pub mod error;
+pub use error::ApiErrorCode;
+pub use error::LoadKeys as LoadKeysError;
 pub use error::Parameter as ParameterError;
 pub use error::Parse as ParseError;
 pub use error::PrivateKey as PrivateKeyError;
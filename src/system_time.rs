@@ -0,0 +1,26 @@
+//! Conversions from the raw timestamps to [`SystemTime`].
+//!
+//! Unlike the `chrono` conversions, these don't saturate. The timestamps sent
+//! by the API are far within the range of [`SystemTime`] on every platform.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Convert milliseconds since the Unix epoch.
+pub fn from_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use pretty_assertions::assert_eq;
+
+    use super::from_millis;
+
+    #[test]
+    fn convert_timestamps() {
+        let expected = UNIX_EPOCH + Duration::from_millis(1_643_883_463_379);
+        assert_eq!(from_millis(1_643_883_463_379), expected);
+    }
+}
@@ -0,0 +1,129 @@
+//! Implementation of the authenticated balances channel.
+
+use futures::{future, Stream, StreamExt};
+use serde::Deserialize;
+
+use crate::{
+    error::SendRequest,
+    http::private::AssetBalance,
+    websocket::{frame::RECONNECTED, Frame, WsClient, WsStream},
+    ApiKeys,
+};
+
+/// Message type of the frames of balance updates.
+pub(crate) const BALANCE_UPDATE: u16 = 201;
+
+impl WsClient {
+    /// Logs in to the feed with the given keys and streams the balances of
+    /// the user as they change, e.g. when an order is placed or matched. This
+    /// is a faster alternative to polling
+    /// [`account_balance`][crate::Client::account_balance] after the fills.
+    ///
+    /// Each update has the balances of the changed assets only.
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or the
+    /// feed rejects the login with a
+    /// [`LoginFailed`][SendRequest::LoginFailed] error. Errors occurring
+    /// afterwards are yielded by the stream.
+    pub async fn subscribe_balances(
+        &self,
+        keys: &ApiKeys,
+    ) -> Result<WsStream<BalanceEvent>, SendRequest> {
+        let frames = self.login(keys).await?;
+        Ok(events(frames))
+    }
+}
+
+/// Item of the balances stream.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BalanceEvent {
+    /// Balances of the assets which changed.
+    Updated(Vec<AssetBalance>),
+    /// Connection dropped and was opened again. Changes during the outage
+    /// are lost so query the
+    /// [`account_balance`][crate::Client::account_balance] to catch up. See
+    /// [`WsClient::reconnect`].
+    Reconnected,
+}
+
+/// **Sample**:
+/// ```json
+#[doc = include_str!("sample.json")]
+/// ```
+#[derive(Deserialize)]
+pub(crate) struct BalanceUpdateRaw {
+    pub(crate) balances: Vec<AssetBalance>,
+}
+
+/// Turn the frames into balance events. Frames of other types are skipped.
+fn events(
+    frames: impl Stream<Item = Result<Frame, SendRequest>> + Send + 'static,
+) -> WsStream<BalanceEvent> {
+    Box::pin(frames.filter_map(|frame| {
+        future::ready(match frame {
+            Ok(frame) => event(frame).transpose(),
+            Err(error) => Some(Err(error)),
+        })
+    }))
+}
+
+/// Turn the frame into a balance event. Returns `None` for the frames of
+/// other types.
+fn event(frame: Frame) -> Result<Option<BalanceEvent>, SendRequest> {
+    Ok(Some(match frame.r#type {
+        BALANCE_UPDATE => {
+            BalanceEvent::Updated(frame.payload::<BalanceUpdateRaw>()?.balances)
+        }
+        RECONNECTED => BalanceEvent::Reconnected,
+        _ => return Ok(None),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::{assert_eq, assert_str_eq};
+
+    use super::{events, BalanceEvent};
+    use crate::{
+        websocket::{Frame, WsClient},
+        ApiKeys,
+    };
+
+    fn frame(json_string: &str) -> Frame {
+        serde_json::from_str::<Frame>(json_string).unwrap()
+    }
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_balances() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let _balances = WsClient::new()
+            .subscribe_balances(&ApiKeys::load_from_env_var())
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn deserialize_balance_events() {
+        let frames = vec![
+            Ok(frame(include_str!("sample.json"))),
+            Ok(frame(include_str!("../user_orders/new_sample.json"))),
+            Ok(Frame::reconnected()),
+        ];
+        let events = block_on(events(stream::iter(frames)).collect::<Vec<_>>())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+
+        let BalanceEvent::Updated(balances) = &events[0] else {
+            panic!("expected a balance update");
+        };
+        assert_eq!(balances.len(), 1);
+        assert_str_eq!(balances[0].asset, "TRY");
+        assert_eq!(balances[0].total(), balances[0].balance);
+        assert_eq!(events[1], BalanceEvent::Reconnected);
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "async-std-runtime")]
+use async_tungstenite::async_std::{connect_async, ConnectStream};
+#[cfg(feature = "tokio-runtime")]
+use async_tungstenite::tokio::{connect_async, ConnectStream};
+use async_tungstenite::{
+    tungstenite::{self, Message},
+    WebSocketSender,
+};
+use futures::{
+    future, lock::Mutex as AsyncMutex, stream, FutureExt, Stream, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use url::Url;
+
+use crate::{error::SendRequest, ApiKeys};
+
+use super::{
+    frame::LOGIN,
+    keepalive::{self, keepalive, Ping},
+    reconnect::{reconnecting, Connect, ReconnectPolicy},
+    Frame,
+};
+
+const BASE: &str = "wss://ws-feed-pro.btcturk.com";
+
+/// Stream of the items received from a websocket channel.
+///
+/// If the connection drops, the stream yields a
+/// [`ConnectionClosed`][SendRequest::ConnectionClosed] error and then ends
+/// unless reconnection is enabled with [`WsClient::reconnect`].
+pub type WsStream<T> =
+    Pin<Box<dyn Stream<Item = Result<T, SendRequest>> + Send>>;
+
+/** Used to subscribe to websocket channels.
+
+Each subscription opens its own connection to the feed. Use
+[`connect`][WsClient::connect] to share a single connection between many
+subscriptions instead. The connection is
+driven by `async-std` by default. Disable the default features and enable the
+`tokio-runtime` feature to subscribe from within a `tokio` runtime instead.
+# Examples
+## Subscribe to a ticker
+```no_run
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+# async_std::task::block_on(async {
+use btcturk::websocket::WsClient;
+use futures::StreamExt;
+
+let client = WsClient::new();
+
+let mut tickers = client.subscribe_ticker("BTCTRY").await?;
+
+while let Some(ticker) = tickers.next().await {
+    println!("Last price of BTCTRY pair is {}", ticker?.last);
+}
+
+# Ok::<(), Box<dyn std::error::Error>>(())
+# })
+# }
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WsClient {
+    url: Url,
+    reconnect: Option<ReconnectPolicy>,
+    keepalive: Option<Duration>,
+    clock_offset: i64,
+}
+
+impl Default for WsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WsClient {
+    /// Construct a client which connects to the `BtcTurk` feed.
+    /// # Panics
+    /// If the hardcoded feed URL can't be parsed, this function will panic.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_url(Url::parse(BASE).expect("failed to parse hardcoded url"))
+    }
+
+    /// Construct a client which connects to the given feed URL.
+    #[must_use]
+    pub const fn with_url(url: Url) -> Self {
+        Self {
+            url,
+            reconnect: None,
+            keepalive: Some(keepalive::DEFAULT_INTERVAL),
+            clock_offset: 0,
+        }
+    }
+
+    /// Open the connection again whenever it drops and subscribe to the same
+    /// channel. The streams let the consumer know about each new connection,
+    /// e.g. the order book stream yields a
+    /// [`Reconnected`][super::OrderBookEvent::Reconnected] event followed by
+    /// a fresh snapshot.
+    /// # Parameters
+    /// - `max_retries`: Number of failed attempts in a row after which the
+    ///   stream gives up and yields the last error.
+    /// - `backoff`: Time to wait before the first attempt. Each subsequent
+    ///   attempt waits twice as long as the previous one.
+    #[must_use]
+    pub const fn reconnect(
+        mut self,
+        max_retries: u32,
+        backoff: Duration,
+    ) -> Self {
+        self.reconnect = Some(ReconnectPolicy::new(max_retries, backoff));
+        self
+    }
+
+    /// Ping the connections which stay silent for the interval and consider
+    /// them dropped if they are still silent an interval after the ping. A
+    /// dropped connection ends the streams with a
+    /// [`ConnectionClosed`][SendRequest::ConnectionClosed] error unless
+    /// [`reconnect`][Self::reconnect] is enabled. Defaults to 30 seconds.
+    /// Pass `None` to disable it.
+    ///
+    /// The connection is only watched while its streams are polled.
+    #[must_use]
+    pub const fn keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.keepalive = interval;
+        self
+    }
+
+    /// Sign the logins of the authenticated channels with the local time
+    /// adjusted by the number of milliseconds the local clock is ahead of
+    /// the server clock, e.g. the one estimated by
+    /// [`Client::clock_offset`][crate::Client::clock_offset]. The feed may
+    /// reject the login if the local clock is off. Defaults to 0.
+    #[must_use]
+    pub const fn clock_offset(mut self, clock_offset: i64) -> Self {
+        self.clock_offset = clock_offset;
+        self
+    }
+
+    /// Get the keepalive interval. See [`keepalive`][Self::keepalive].
+    #[must_use]
+    pub const fn keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive
+    }
+
+    /// Get a reference to the feed URL.
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Open a new connection and join the channel with the given event.
+    pub(crate) async fn subscribe(
+        &self,
+        channel: &str,
+        event: &str,
+    ) -> Result<WsStream<Frame>, SendRequest> {
+        let url = self.url.clone();
+        let keepalive = self.keepalive;
+        let text = Frame::subscription(channel, event, true);
+        self.establish(Arc::new(move || {
+            open(url.clone(), keepalive, text.clone()).boxed()
+        }))
+        .await
+    }
+
+    /// Open a new connection and authenticate it with the given keys. The
+    /// returned stream starts after the result of the login.
+    pub(crate) async fn login(
+        &self,
+        keys: &ApiKeys,
+    ) -> Result<WsStream<Frame>, SendRequest> {
+        let url = self.url.clone();
+        let keepalive = self.keepalive;
+        let clock_offset = self.clock_offset;
+        let keys = keys.clone();
+        self.establish(Arc::new(move || {
+            let url = url.clone();
+            let keys = keys.clone();
+            async move {
+                let text = Frame::login(&keys, clock_offset)?;
+                let frames = open(url, keepalive, text).await?;
+                authenticated(frames).await
+            }
+            .boxed()
+        }))
+        .await
+    }
+
+    /// Open the first connection and keep reconnecting afterwards if
+    /// enabled.
+    pub(crate) async fn establish(
+        &self,
+        connect: Connect,
+    ) -> Result<WsStream<Frame>, SendRequest> {
+        let frames = connect().await?;
+        Ok(match self.reconnect {
+            Some(policy) => reconnecting(frames, connect, policy),
+            None => frames,
+        })
+    }
+}
+
+/// Open a new connection and send the given frame.
+async fn open(
+    url: Url,
+    keepalive: Option<Duration>,
+    text: String,
+) -> Result<WsStream<Frame>, SendRequest> {
+    let slot = Arc::new(AsyncMutex::new(None));
+    let ping = keepalive::ping(Arc::clone(&slot));
+    let (mut sink, frames) = open_split(&url, keepalive, ping).await?;
+    sink.send(Message::text(text)).await?;
+    *slot.lock().await = Some(sink);
+    Ok(frames)
+}
+
+/// Writing half of a connection.
+pub type Sink = WebSocketSender<ConnectStream>;
+
+/// Open a new connection and split it into the writing half and the stream
+/// of the received frames. The connection is pinged with the given
+/// [`Ping`] when it stays silent for the keepalive interval.
+pub async fn open_split(
+    url: &Url,
+    interval: Option<Duration>,
+    ping: Ping,
+) -> Result<(Sink, WsStream<Frame>), SendRequest> {
+    let (socket, _) = connect_async(url.as_str()).await?;
+    let (sink, stream) = socket.split();
+    Ok((sink, Box::pin(frames(keepalive(stream, interval, ping)))))
+}
+
+#[derive(Deserialize)]
+struct LoginResult {
+    ok: bool,
+    message: Option<String>,
+}
+
+/// Wait for the result of the login and return the rest of the frames if it
+/// succeeded.
+async fn authenticated<S>(mut frames: S) -> Result<S, SendRequest>
+where
+    S: Stream<Item = Result<Frame, SendRequest>> + Unpin,
+{
+    loop {
+        match frames.next().await {
+            Some(Ok(frame)) if frame.r#type == LOGIN => {
+                let result = frame.payload::<LoginResult>()?;
+                if result.ok {
+                    return Ok(frames);
+                }
+                return Err(SendRequest::LoginFailed {
+                    message: result.message,
+                });
+            }
+            Some(Ok(_)) => {}
+            Some(Err(error)) => return Err(error),
+            None => return Err(SendRequest::ConnectionClosed),
+        }
+    }
+}
+
+/// Turn the socket into a stream of frames. Control messages are skipped.
+fn frames<S>(socket: S) -> impl Stream<Item = Result<Frame, SendRequest>> + Send
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin + Send,
+{
+    stream::unfold(Some(socket), |socket| async move {
+        let mut socket = socket?;
+        loop {
+            let frame = match socket.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    log::debug!("websocket frame: {text}");
+                    serde_json::from_str::<Frame>(&text).map_err(Into::into)
+                }
+                Some(Ok(Message::Close(_))) | None => {
+                    return Some((Err(SendRequest::ConnectionClosed), None));
+                }
+                Some(Ok(_)) => continue,
+                Some(Err(error)) => return Some((Err(error.into()), None)),
+            };
+            return Some((frame, Some(socket)));
+        }
+    })
+}
+
+/// Keep only the frames with the given type and deserialize their payloads.
+pub fn channel<R, T>(
+    frames: impl Stream<Item = Result<Frame, SendRequest>> + Send + 'static,
+    r#type: u16,
+) -> WsStream<T>
+where
+    R: DeserializeOwned,
+    T: From<R> + Send + 'static,
+{
+    Box::pin(frames.filter_map(move |frame| {
+        future::ready(match frame {
+            Ok(frame) if frame.r#type == r#type => {
+                Some(frame.payload::<R>().map(T::from).map_err(Into::into))
+            }
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+    }))
+}
+
+/// Local time in milliseconds. Used for the items which the feed sends
+/// without a timestamp.
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| {
+            duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, stream, StreamExt};
+
+    use super::authenticated;
+    use crate::{error::SendRequest, websocket::Frame};
+
+    fn frame(json_string: &str) -> Result<Frame, SendRequest> {
+        serde_json::from_str::<Frame>(json_string).map_err(Into::into)
+    }
+
+    #[test]
+    fn login_succeeded() {
+        let frames = stream::iter(vec![
+            frame(r#"[991, {"type": 991}]"#),
+            frame(r#"[114, {"type": 114, "ok": true, "message": "success"}]"#),
+            frame(r#"[451, {"type": 451}]"#),
+        ]);
+        let mut frames = block_on(authenticated(frames)).unwrap();
+        let next = block_on(frames.next()).unwrap().unwrap();
+        assert_eq!(next.r#type, 451);
+    }
+
+    #[test]
+    fn login_failed() {
+        let frames = stream::iter(vec![frame(
+            r#"[114, {"type": 114, "ok": false, "message": "invalid"}]"#,
+        )]);
+        let Err(SendRequest::LoginFailed { message }) =
+            block_on(authenticated(frames))
+        else {
+            panic!("expected a login failure");
+        };
+        assert_eq!(message.as_deref(), Some("invalid"));
+    }
+}
@@ -0,0 +1,520 @@
+//! Multiplexing of the websocket subscriptions over a single connection.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use async_tungstenite::tungstenite::Message;
+use futures::{
+    lock::Mutex as AsyncMutex,
+    task::{self, ArcWake},
+    FutureExt, Stream, StreamExt,
+};
+use url::Url;
+
+use crate::error::SendRequest;
+
+use super::{
+    client::{open_split, Sink},
+    frame::RECONNECTED,
+    keepalive, Frame, WsClient, WsStream,
+};
+
+impl WsClient {
+    /** Open a single connection to the feed which can carry any number of
+    subscriptions. See [`WsConnection`].
+
+    If reconnection is enabled with [`WsClient::reconnect`], all the channels
+    which are subscribed at the time the connection drops are subscribed again
+    on the new connection.
+    # Errors
+    [`SendRequest`] if there is an error connecting to the feed.
+    # Examples
+    ```no_run
+    # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    # async_std::task::block_on(async {
+    use btcturk::websocket::WsClient;
+    use futures::StreamExt;
+
+    let connection = WsClient::new().connect().await?;
+
+    let mut btc = connection.subscribe_ticker("BTCTRY").await?;
+    let mut eth = connection.subscribe_ticker("ETHTRY").await?;
+
+    println!("Last price of BTCTRY pair is {}", btc.next().await.unwrap()?.last);
+    println!("Last price of ETHTRY pair is {}", eth.next().await.unwrap()?.last);
+
+    # Ok::<(), Box<dyn std::error::Error>>(())
+    # })
+    # }
+    ```
+    */
+    pub async fn connect(&self) -> Result<WsConnection, SendRequest> {
+        let session = Arc::new(Session::default());
+        let url = self.url().clone();
+        let keepalive = self.keepalive_interval();
+        let frames = {
+            let session = Arc::clone(&session);
+            self.establish(Arc::new(move || {
+                Arc::clone(&session).open(url.clone(), keepalive).boxed()
+            }))
+            .await?
+        };
+        Ok(WsConnection::new(session, frames))
+    }
+}
+
+/// Channels of the feed which can be subscribed to over a [`WsConnection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Channel {
+    /// See [`WsConnection::subscribe_ticker`].
+    Ticker,
+    /// See [`WsConnection::subscribe_order_book`].
+    OrderBook,
+    /// See [`WsConnection::subscribe_trades`].
+    Trade,
+}
+
+impl Channel {
+    /// Name of the channel in the subscription frames.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ticker => "ticker",
+            Self::OrderBook => "obdiff",
+            Self::Trade => "trade",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Ticker, Self::OrderBook, Self::Trade]
+            .into_iter()
+            .find(|channel| channel.name() == name)
+    }
+}
+
+/// Channel and event (usually the pair) of a subscription.
+type Topic = (Channel, String);
+
+/** Single connection to the feed which carries many subscriptions.
+
+Each subscription returns its own [`WsStream`] and the received frames are
+routed to the streams by their channel and pair. The frames are read from the
+connection while any of the streams is polled, so a stream which is never
+polled only buffers its frames and doesn't hold the others back.
+
+Dropping a stream stops routing frames to it but the feed keeps sending them
+until the channel is left with [`unsubscribe`][Self::unsubscribe]. Frames
+which can't be parsed are logged and skipped. Use
+[`messages`][Self::messages] to receive all the frames of the connection as
+they arrive.
+
+Constructed with [`WsClient::connect`]. Cloning is cheap and the clones share
+the same connection.
+*/
+#[derive(Clone)]
+pub struct WsConnection {
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for WsConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WsConnection").finish_non_exhaustive()
+    }
+}
+
+impl WsConnection {
+    fn new(session: Arc<Session>, frames: WsStream<Frame>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                session,
+                router: Mutex::new(Router {
+                    frames,
+                    routes: HashMap::new(),
+                    next_id: 0,
+                    closed: false,
+                }),
+                wakers: Arc::default(),
+            }),
+        }
+    }
+
+    /// Join the channel with the given event unless it is already joined
+    /// and return the stream of its frames.
+    pub(crate) async fn subscribe(
+        &self,
+        channel: Channel,
+        event: String,
+    ) -> Result<WsStream<Frame>, SendRequest> {
+        let session = &self.shared.session;
+        let mut sink = session.sink.lock().await;
+        let (routed, first) = self.route(Some((channel, event.clone())));
+        if first {
+            let text = Frame::subscription(channel.name(), &event, true);
+            Session::send(&mut sink, text).await?;
+        }
+        Ok(Box::pin(routed))
+    }
+
+    /// Stream of all the frames received over this connection.
+    pub(crate) fn frames(&self) -> WsStream<Frame> {
+        let (routed, _) = self.route(None);
+        Box::pin(routed)
+    }
+
+    /// Construct a connection which reads the given frames and can't send
+    /// anything.
+    #[cfg(test)]
+    pub(crate) fn from_frames(frames: WsStream<Frame>) -> Self {
+        Self::new(Arc::default(), frames)
+    }
+
+    /// Leave the channel of the given pair. All the streams subscribed to it
+    /// end after yielding the frames they have already received.
+    /// # Errors
+    /// [`SendRequest`] if there is an error sending the frame which leaves
+    /// the channel.
+    pub async fn unsubscribe(
+        &self,
+        channel: Channel,
+        pair: impl Into<String> + Send,
+    ) -> Result<(), SendRequest> {
+        let topic = (channel, pair.into());
+        let session = &self.shared.session;
+        let mut sink = session.sink.lock().await;
+        if lock(&session.topics).remove(&topic).is_none() {
+            return Ok(());
+        }
+        lock(&self.shared.router)
+            .routes
+            .retain(|_, route| route.topic.as_ref() != Some(&topic));
+        self.shared.wakers.wake_all();
+        let text = Frame::subscription(channel.name(), &topic.1, false);
+        Session::send(&mut sink, text).await
+    }
+
+    /// Register a new stream for the topic, or for all the frames if there
+    /// is no topic. Returns whether the topic is new and has to be joined.
+    fn route(&self, topic: Option<Topic>) -> (Routed, bool) {
+        let id = {
+            let mut router = lock(&self.shared.router);
+            let id = router.next_id;
+            router.next_id += 1;
+            router.routes.insert(
+                id,
+                Route {
+                    topic: topic.clone(),
+                    queue: VecDeque::new(),
+                },
+            );
+            id
+        };
+        let first = topic.is_some_and(|topic| {
+            let count = *lock(&self.shared.session.topics)
+                .entry(topic)
+                .and_modify(|count| *count += 1)
+                .or_insert(1);
+            count == 1
+        });
+        let routed = Routed {
+            shared: Arc::clone(&self.shared),
+            id,
+        };
+        (routed, first)
+    }
+}
+
+/// State which is needed to open the connection again.
+#[derive(Default)]
+struct Session {
+    sink: Arc<AsyncMutex<Option<Sink>>>,
+    /// Number of streams of each subscribed topic.
+    topics: Mutex<HashMap<Topic, usize>>,
+}
+
+impl Session {
+    /// Open a new connection and join all the subscribed topics.
+    async fn open(
+        self: Arc<Self>,
+        url: Url,
+        keepalive: Option<Duration>,
+    ) -> Result<WsStream<Frame>, SendRequest> {
+        let ping = keepalive::ping(Arc::clone(&self.sink));
+        let (mut new_sink, frames) = open_split(&url, keepalive, ping).await?;
+        let mut sink = self.sink.lock().await;
+        let topics = lock(&self.topics).keys().cloned().collect::<Vec<_>>();
+        for (channel, event) in topics {
+            let text = Frame::subscription(channel.name(), &event, true);
+            new_sink.send(Message::text(text)).await?;
+        }
+        *sink = Some(new_sink);
+        drop(sink);
+        Ok(frames)
+    }
+
+    async fn send(
+        sink: &mut Option<Sink>,
+        text: String,
+    ) -> Result<(), SendRequest> {
+        let sink = sink.as_mut().ok_or(SendRequest::ConnectionClosed)?;
+        sink.send(Message::text(text)).await?;
+        Ok(())
+    }
+
+    /// Forget a stream of the topic.
+    fn release(&self, topic: &Topic) {
+        let mut topics = lock(&self.topics);
+        if let Some(count) = topics.get_mut(topic) {
+            *count -= 1;
+            if *count == 0 {
+                topics.remove(topic);
+            }
+        }
+    }
+}
+
+struct Shared {
+    session: Arc<Session>,
+    router: Mutex<Router>,
+    wakers: Arc<Wakers>,
+}
+
+struct Router {
+    frames: WsStream<Frame>,
+    routes: HashMap<u64, Route>,
+    next_id: u64,
+    closed: bool,
+}
+
+struct Route {
+    /// `None` if the stream receives all the frames.
+    topic: Option<Topic>,
+    queue: VecDeque<Result<Frame, SendRequest>>,
+}
+
+impl Router {
+    /// Queue the item for the streams it belongs to.
+    fn dispatch(&mut self, item: Result<Frame, SendRequest>) {
+        match item {
+            Ok(frame) if frame.r#type == RECONNECTED => {
+                for route in self.routes.values_mut() {
+                    route.queue.push_back(Ok(frame.clone()));
+                }
+            }
+            Ok(frame) => {
+                let topic = topic(&frame);
+                let mut routed = false;
+                for route in self.routes.values_mut() {
+                    if route.topic.is_none() || route.topic == topic {
+                        route.queue.push_back(Ok(frame.clone()));
+                        routed = true;
+                    }
+                }
+                if !routed {
+                    log::debug!("skipped websocket frame: {frame:?}");
+                }
+            }
+            Err(
+                error @ (SendRequest::ConnectionClosed
+                | SendRequest::WebsocketError { .. }),
+            ) => {
+                log::warn!("websocket connection dropped: {error}");
+                for route in self.routes.values_mut() {
+                    route.queue.push_back(Err(SendRequest::ConnectionClosed));
+                }
+            }
+            Err(error) => log::warn!("skipped websocket frame: {error}"),
+        }
+    }
+}
+
+impl Router {
+    /// Take the next item of the stream with the given id. Reads from the
+    /// connection until the stream has an item. All the streams are woken
+    /// when the connection has something new.
+    fn poll_route(
+        &mut self,
+        id: u64,
+        cx: &Context<'_>,
+        wakers: &Arc<Wakers>,
+    ) -> Poll<Option<Result<Frame, SendRequest>>> {
+        loop {
+            let Some(route) = self.routes.get_mut(&id) else {
+                return Poll::Ready(None);
+            };
+            if let Some(item) = route.queue.pop_front() {
+                return Poll::Ready(Some(item));
+            }
+            if self.closed {
+                return Poll::Ready(None);
+            }
+            wakers.register(id, cx.waker());
+            let waker = task::waker(Arc::clone(wakers));
+            match self
+                .frames
+                .poll_next_unpin(&mut Context::from_waker(&waker))
+            {
+                Poll::Ready(Some(item)) => self.dispatch(item),
+                Poll::Ready(None) => self.closed = true,
+                Poll::Pending => return Poll::Pending,
+            }
+            wakers.wake_all();
+        }
+    }
+}
+
+/// Read the topic from the payload of a channel frame.
+fn topic(frame: &Frame) -> Option<Topic> {
+    let channel = Channel::from_name(frame.payload.get("channel")?.as_str()?)?;
+    let event = frame.payload.get("event")?.as_str()?;
+    Some((channel, event.to_owned()))
+}
+
+/// Wakes all the streams when the connection has something new.
+#[derive(Default)]
+struct Wakers(Mutex<HashMap<u64, Waker>>);
+
+impl Wakers {
+    fn register(&self, id: u64, waker: &Waker) {
+        lock(&self.0).insert(id, waker.clone());
+    }
+
+    fn remove(&self, id: u64) {
+        lock(&self.0).remove(&id);
+    }
+
+    fn wake_all(&self) {
+        let wakers = std::mem::take(&mut *lock(&self.0));
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+impl ArcWake for Wakers {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.wake_all();
+    }
+}
+
+/// Stream of the frames of a single subscription.
+struct Routed {
+    shared: Arc<Shared>,
+    id: u64,
+}
+
+impl Stream for Routed {
+    type Item = Result<Frame, SendRequest>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let shared = &self.shared;
+        lock(&shared.router).poll_route(self.id, cx, &shared.wakers)
+    }
+}
+
+impl Drop for Routed {
+    fn drop(&mut self) {
+        let route = lock(&self.shared.router).routes.remove(&self.id);
+        self.shared.wakers.remove(self.id);
+        if let Some(topic) = route.and_then(|route| route.topic) {
+            self.shared.session.release(&topic);
+        }
+    }
+}
+
+/// Keep using the state even if another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::assert_eq;
+
+    use super::{lock, Channel, WsConnection};
+    use crate::{
+        error::SendRequest,
+        websocket::{frame::RECONNECTED, Frame, WsStream},
+    };
+
+    fn frame(json_string: &str) -> Result<Frame, SendRequest> {
+        serde_json::from_str::<Frame>(json_string).map_err(Into::into)
+    }
+
+    fn types(frames: WsStream<Frame>) -> Vec<Option<u16>> {
+        block_on(frames.collect::<Vec<_>>())
+            .into_iter()
+            .map(|frame| frame.ok().map(|frame| frame.r#type))
+            .collect()
+    }
+
+    fn connection() -> WsConnection {
+        let frames = stream::iter(vec![
+            frame(r#"[402, {"channel": "ticker", "event": "BTCTRY"}]"#),
+            frame(r#"[991, {"type": 991}]"#),
+            frame(r#"[402, {"channel": "ticker", "event": "ETHTRY"}]"#),
+            Ok(Frame::reconnected()),
+            frame(r#"[422, {"channel": "trade", "event": "BTCTRY"}]"#),
+            Err(SendRequest::ConnectionClosed),
+        ]);
+        WsConnection::new(Arc::default(), Box::pin(frames))
+    }
+
+    #[test]
+    fn route_frames_by_topic() {
+        let connection = connection();
+        let (btc, first) =
+            connection.route(Some((Channel::Ticker, "BTCTRY".to_owned())));
+        assert!(first);
+        let (eth, _) =
+            connection.route(Some((Channel::Ticker, "ETHTRY".to_owned())));
+        let (trades, _) =
+            connection.route(Some((Channel::Trade, "BTCTRY".to_owned())));
+        let (same, first) =
+            connection.route(Some((Channel::Ticker, "BTCTRY".to_owned())));
+        assert!(!first);
+
+        assert_eq!(
+            types(Box::pin(btc)),
+            vec![Some(402), Some(RECONNECTED), None]
+        );
+        assert_eq!(
+            types(Box::pin(eth)),
+            vec![Some(402), Some(RECONNECTED), None]
+        );
+        assert_eq!(
+            types(Box::pin(trades)),
+            vec![Some(RECONNECTED), Some(422), None]
+        );
+        assert_eq!(
+            types(Box::pin(same)),
+            vec![Some(402), Some(RECONNECTED), None]
+        );
+        assert!(lock(&connection.shared.session.topics).is_empty());
+    }
+
+    #[test]
+    fn unsubscribe_ends_streams() {
+        let connection = connection();
+        let (btc, _) =
+            connection.route(Some((Channel::Ticker, "BTCTRY".to_owned())));
+        let (trades, _) =
+            connection.route(Some((Channel::Trade, "BTCTRY".to_owned())));
+        let result =
+            block_on(connection.unsubscribe(Channel::Ticker, "BTCTRY"));
+        assert!(matches!(result, Err(SendRequest::ConnectionClosed)));
+        assert_eq!(types(Box::pin(btc)), vec![]);
+        assert_eq!(types(Box::pin(trades)).len(), 3);
+    }
+}
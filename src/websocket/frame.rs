@@ -0,0 +1,129 @@
+//! General websocket frame implementation.
+
+use std::time::SystemTimeError;
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+
+use crate::{http::nonce, ApiKeys};
+
+/// Message type of the local frames which mark a new connection. The feed
+/// doesn't send frames of this type.
+pub const RECONNECTED: u16 = 0;
+/// Message type of the login request and result frames.
+pub const LOGIN: u16 = 114;
+/// Message type of the subscription frames.
+pub const SUBSCRIBE: u16 = 151;
+
+/// Every message in the feed is an array of two items: message type and
+/// payload, respectively.
+///
+/// **Sample**:
+/// ```json
+/// [151, {"type": 151, "channel": "ticker", "event": "BTCTRY", "join": true}]
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "FrameRaw")]
+pub struct Frame {
+    pub r#type: u16,
+    pub payload: Value,
+}
+
+impl From<FrameRaw> for Frame {
+    fn from(raw: FrameRaw) -> Self {
+        Self {
+            r#type: raw.0,
+            payload: raw.1,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FrameRaw(u16, Value);
+
+impl Frame {
+    /// Build the frame which is yielded after a dropped connection is opened
+    /// again.
+    pub const fn reconnected() -> Self {
+        Self {
+            r#type: RECONNECTED,
+            payload: Value::Null,
+        }
+    }
+
+    /// Build the text of a frame which joins or leaves a channel.
+    pub fn subscription(channel: &str, event: &str, join: bool) -> String {
+        json!([
+            SUBSCRIBE,
+            {
+                "type": SUBSCRIBE,
+                "channel": channel,
+                "event": event,
+                "join": join,
+            }
+        ])
+        .to_string()
+    }
+
+    /// Build the text of a frame which authenticates the connection. The
+    /// current timestamp adjusted by the `clock_offset` is used as the nonce
+    /// of the signature, the same way as the HTTP requests are signed.
+    pub fn login(
+        keys: &ApiKeys,
+        clock_offset: i64,
+    ) -> Result<String, SystemTimeError> {
+        let nonce = nonce(clock_offset)?;
+        let signature = keys.sign(nonce);
+        Ok(json!([
+            LOGIN,
+            {
+                "type": LOGIN,
+                "publicKey": keys.public_key(),
+                "timestamp": nonce,
+                "nonce": nonce,
+                "signature": signature,
+            }
+        ])
+        .to_string())
+    }
+
+    /// Deserialize the payload into the given type.
+    pub fn payload<D: DeserializeOwned>(self) -> Result<D, serde_json::Error> {
+        serde_json::from_value(self.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame, LOGIN, SUBSCRIBE};
+    use crate::http::{mock, nonce};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn subscription_round_trip() {
+        let text = Frame::subscription("ticker", "BTCTRY", true);
+        let frame = serde_json::from_str::<Frame>(&text).unwrap();
+        assert_eq!(frame.r#type, SUBSCRIBE);
+        assert_eq!(frame.payload["channel"], "ticker");
+        assert_eq!(frame.payload["event"], "BTCTRY");
+        assert_eq!(frame.payload["join"], true);
+    }
+
+    #[test]
+    fn login_frame() {
+        const CLOCK_OFFSET: i64 = 3_600_000;
+        let text = Frame::login(&mock::keys(), CLOCK_OFFSET).unwrap();
+        let frame = serde_json::from_str::<Frame>(&text).unwrap();
+        assert_eq!(frame.r#type, LOGIN);
+        assert_eq!(
+            frame.payload["publicKey"],
+            "63762e79-cb5c-4c0b-b714-5f0ce94bf100"
+        );
+        assert_eq!(frame.payload["nonce"], frame.payload["timestamp"]);
+        let signed = frame.payload["nonce"].as_u64().unwrap();
+        let skew =
+            signed.abs_diff(nonce(0).unwrap() - CLOCK_OFFSET.unsigned_abs());
+        assert!(skew < 1000, "{skew}");
+        assert!(frame.payload["signature"].is_string());
+    }
+}
@@ -0,0 +1,145 @@
+//! Keepalive of the websocket connections.
+
+use std::{sync::Arc, time::Duration};
+
+use async_tungstenite::tungstenite::{self, Message};
+use futures::{
+    future::{self, BoxFuture, Either},
+    lock::Mutex as AsyncMutex,
+    stream::{self, BoxStream},
+    FutureExt, Stream, StreamExt,
+};
+use futures_timer::Delay;
+
+use crate::error::SendRequest;
+
+use super::client::Sink;
+
+/// Time a connection may stay silent before it is pinged, unless set with
+/// [`WsClient::keepalive`][super::WsClient::keepalive].
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Sends a ping over the connection.
+pub type Ping =
+    Arc<dyn Fn() -> BoxFuture<'static, Result<(), SendRequest>> + Send + Sync>;
+
+/// Build a [`Ping`] which sends a ping message through the sink in the
+/// slot. Nothing is sent while the slot is empty.
+pub fn ping(slot: Arc<AsyncMutex<Option<Sink>>>) -> Ping {
+    Arc::new(move || {
+        let slot = Arc::clone(&slot);
+        async move {
+            if let Some(sink) = slot.lock().await.as_mut() {
+                sink.send(Message::Ping(Vec::new().into())).await?;
+            }
+            Ok(())
+        }
+        .boxed()
+    })
+}
+
+/// Pass the messages through and ping the connection whenever it stays
+/// silent for the interval. Any message, including the pong, counts. If the
+/// connection is still silent an interval after the ping, it is considered
+/// dropped and the stream ends.
+pub fn keepalive<S>(
+    messages: S,
+    interval: Option<Duration>,
+    ping: Ping,
+) -> BoxStream<'static, Result<Message, tungstenite::Error>>
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>>
+        + Unpin
+        + Send
+        + 'static,
+{
+    let Some(interval) = interval else {
+        return messages.boxed();
+    };
+    stream::unfold(Some(messages), move |messages| {
+        let ping = Arc::clone(&ping);
+        async move {
+            let mut messages = messages?;
+            let mut pinged = false;
+            loop {
+                match future::select(messages.next(), Delay::new(interval))
+                    .await
+                {
+                    Either::Left((item, _)) => {
+                        return item.map(|item| (item, Some(messages)));
+                    }
+                    Either::Right(_) if pinged => {
+                        log::warn!(
+                            "websocket connection is silent for {interval:?} \
+                            after a ping"
+                        );
+                        return None;
+                    }
+                    Either::Right(_) => {
+                        if let Err(error) = ping().await {
+                            log::warn!("failed to ping the websocket: {error}");
+                            return None;
+                        }
+                        pinged = true;
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use async_tungstenite::tungstenite::Message;
+    use futures::{stream, FutureExt, StreamExt};
+    use pretty_assertions::assert_eq;
+
+    use super::{keepalive, Ping};
+
+    fn counting_ping(pings: &Arc<AtomicUsize>) -> Ping {
+        let pings = Arc::clone(pings);
+        Arc::new(move || {
+            pings.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }.boxed()
+        })
+    }
+
+    #[async_std::test]
+    async fn end_silent_connection_after_ping() {
+        let pings = Arc::new(AtomicUsize::new(0));
+        let messages = stream::iter(vec![Ok(Message::text("[]"))])
+            .chain(stream::pending());
+        let items = keepalive(
+            messages,
+            Some(Duration::from_millis(10)),
+            counting_ping(&pings),
+        )
+        .collect::<Vec<_>>()
+        .await;
+        assert_eq!(items.len(), 1);
+        assert_eq!(pings.load(Ordering::SeqCst), 1);
+    }
+
+    #[async_std::test]
+    async fn pass_messages_through_without_interval() {
+        let pings = Arc::new(AtomicUsize::new(0));
+        let messages = stream::iter(vec![
+            Ok(Message::Pong(Vec::new().into())),
+            Ok(Message::text("[]")),
+        ]);
+        let items = keepalive(messages, None, counting_ping(&pings))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(pings.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,197 @@
+//! Typed items of the merged feed of a connection.
+
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    error::SendRequest,
+    http::{
+        private::AssetBalance,
+        public::{OrderBook, Ticker, Trade},
+    },
+};
+
+use super::{
+    balances::{BalanceUpdateRaw, BALANCE_UPDATE},
+    frame::RECONNECTED,
+    order_book::{OrderBookRaw, ORDER_BOOK_DIFF, ORDER_BOOK_FULL},
+    ticker::{TickerAllRaw, TickerRaw, TICKER_ALL, TICKER_PAIR},
+    trades::{TradeRaw, TRADE_SINGLE},
+    user_orders::{self, ORDER_DELETE, ORDER_INSERT, ORDER_MATCHED},
+    Frame, OrderBookUpdate, UserOrderEvent, WsConnection, WsStream,
+};
+
+impl WsConnection {
+    /// Streams all the frames received over this connection, whichever
+    /// subscription they belong to, as typed messages.
+    ///
+    /// The frames are dispatched on their message type. A frame whose payload
+    /// can't be parsed is yielded as an error and the stream goes on.
+    #[must_use]
+    pub fn messages(&self) -> WsStream<WsMessage> {
+        Box::pin(
+            self.frames()
+                .map(|frame| frame.and_then(WsMessage::try_from)),
+        )
+    }
+}
+
+/// Item of the merged feed of a [`WsConnection`].
+///
+/// Can also be deserialized from the text of a frame, e.g.
+/// `[402, {"PS": "BTCTRY", ...}]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "Frame")]
+pub enum WsMessage {
+    /// See [`WsClient::subscribe_ticker`][super::WsClient::subscribe_ticker].
+    Ticker(Ticker),
+    /// See
+    /// [`WsClient::subscribe_all_tickers`][super::WsClient::subscribe_all_tickers].
+    AllTickers(Vec<Ticker>),
+    /// Whole order book of a pair. Its [`timestamp`][OrderBook::timestamp] is
+    /// the local time the snapshot was received at.
+    OrderBookSnapshot(OrderBook),
+    /// Changes in the order book of a pair. The sequence numbers are not
+    /// checked.
+    OrderBookDiff(OrderBookUpdate),
+    /// See [`WsClient::subscribe_trades`][super::WsClient::subscribe_trades].
+    Trade(Trade),
+    /// See
+    /// [`WsClient::subscribe_user_orders`][super::WsClient::subscribe_user_orders].
+    UserOrder(UserOrderEvent),
+    /// See
+    /// [`WsClient::subscribe_balances`][super::WsClient::subscribe_balances].
+    Balances(Vec<AssetBalance>),
+    /// Connection dropped and was opened again. See
+    /// [`WsClient::reconnect`][super::WsClient::reconnect].
+    Reconnected,
+    /// Frame of a type which is not known by this crate, e.g. the result of
+    /// a subscription.
+    Unknown {
+        /// Message type of the frame.
+        type_index: u16,
+        /// Payload of the frame.
+        raw: Value,
+    },
+}
+
+impl TryFrom<Frame> for WsMessage {
+    type Error = SendRequest;
+
+    fn try_from(frame: Frame) -> Result<Self, Self::Error> {
+        Ok(match frame.r#type {
+            TICKER_PAIR => Self::Ticker(frame.payload::<TickerRaw>()?.into()),
+            TICKER_ALL => {
+                Self::AllTickers(frame.payload::<TickerAllRaw>()?.into())
+            }
+            ORDER_BOOK_FULL => Self::OrderBookSnapshot(
+                frame.payload::<OrderBookRaw>()?.into_snapshot(),
+            ),
+            ORDER_BOOK_DIFF => Self::OrderBookDiff(
+                frame.payload::<OrderBookRaw>()?.into_update(),
+            ),
+            TRADE_SINGLE => Self::Trade(frame.payload::<TradeRaw>()?.into()),
+            ORDER_INSERT | ORDER_MATCHED | ORDER_DELETE => {
+                match user_orders::event(frame)? {
+                    Ok(event) => Self::UserOrder(event),
+                    Err(frame) => Self::Unknown {
+                        type_index: frame.r#type,
+                        raw: frame.payload,
+                    },
+                }
+            }
+            BALANCE_UPDATE => {
+                Self::Balances(frame.payload::<BalanceUpdateRaw>()?.balances)
+            }
+            RECONNECTED => Self::Reconnected,
+            type_index => Self::Unknown {
+                type_index,
+                raw: frame.payload,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::{assert_eq, assert_str_eq};
+
+    use super::WsMessage;
+    use crate::{
+        error::SendRequest,
+        websocket::{Frame, UserOrderEvent, WsConnection},
+    };
+
+    fn message(json_string: &str) -> WsMessage {
+        serde_json::from_str::<WsMessage>(json_string).unwrap()
+    }
+
+    #[test]
+    fn deserialize_messages() {
+        let WsMessage::Ticker(ticker) =
+            message(include_str!("ticker/sample.json"))
+        else {
+            panic!("expected a ticker");
+        };
+        assert_str_eq!(ticker.pair, "BTCUSDT");
+        let WsMessage::AllTickers(tickers) =
+            message(include_str!("ticker/all_sample.json"))
+        else {
+            panic!("expected all tickers");
+        };
+        assert_eq!(tickers.len(), 2);
+        let WsMessage::OrderBookSnapshot(_) =
+            message(include_str!("order_book/snapshot_sample.json"))
+        else {
+            panic!("expected an order book snapshot");
+        };
+        let WsMessage::OrderBookDiff(update) =
+            message(include_str!("order_book/update_sample.json"))
+        else {
+            panic!("expected an order book difference");
+        };
+        assert_eq!(update.change_set, 1025);
+        let WsMessage::Trade(trade) =
+            message(include_str!("trades/sample.json"))
+        else {
+            panic!("expected a trade");
+        };
+        assert_str_eq!(trade.pair, "BTCUSDT");
+        let WsMessage::UserOrder(UserOrderEvent::Matched(_)) =
+            message(include_str!("user_orders/matched_sample.json"))
+        else {
+            panic!("expected a user order match");
+        };
+        let WsMessage::Balances(balances) =
+            message(include_str!("balances/sample.json"))
+        else {
+            panic!("expected balances");
+        };
+        assert_eq!(balances.len(), 1);
+        let WsMessage::Unknown { type_index, raw } =
+            message(r#"[100, {"ok": true}]"#)
+        else {
+            panic!("expected an unknown message");
+        };
+        assert_eq!(type_index, 100);
+        assert_eq!(raw["ok"], true);
+    }
+
+    #[test]
+    fn keep_streaming_after_malformed_payload() {
+        let frames = stream::iter(vec![
+            serde_json::from_str::<Frame>(r#"[422, {"PS": "BTCTRY"}]"#)
+                .map_err(Into::into),
+            Ok(Frame::reconnected()),
+            Err(SendRequest::ConnectionClosed),
+        ]);
+        let connection = WsConnection::from_frames(Box::pin(frames));
+        let messages = block_on(connection.messages().collect::<Vec<_>>());
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].is_err());
+        assert_eq!(messages[1].as_ref().ok(), Some(&WsMessage::Reconnected));
+        assert!(matches!(messages[2], Err(SendRequest::ConnectionClosed)));
+    }
+}
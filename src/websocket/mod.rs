@@ -1,5 +1,37 @@
-//! Websocket feed implementation. Not implemented yet.
+//! Websocket feed related section of the API is implemented under this
+//! module.
+//!
+//! In this section, there is a [`WsClient`] which subscribes to the channels
+//! of the feed and returns a [`WsStream`] of the received items. Many
+//! subscriptions can share a single connection through a [`WsConnection`].
 //!
 //! See <https://docs.btcturk.com/websocket-feed/protocol>.
 
-//pub(crate) const WEBSOCKET_BASE: &str = "wss://ws-feed-pro.btcturk.com";
+mod frame;
+pub(crate) use frame::Frame;
+
+mod reconnect;
+
+mod keepalive;
+
+mod client;
+pub use client::{WsClient, WsStream};
+
+mod connection;
+pub use connection::{Channel, WsConnection};
+
+mod message;
+pub use message::WsMessage;
+
+pub mod ticker;
+
+pub mod trades;
+
+pub mod order_book;
+pub use order_book::{OrderBookEvent, OrderBookUpdate};
+
+pub mod user_orders;
+pub use user_orders::{OrderMatch, UserOrder, UserOrderEvent};
+
+pub mod balances;
+pub use balances::BalanceEvent;
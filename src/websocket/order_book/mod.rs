@@ -0,0 +1,428 @@
+//! Implementation of the order book difference channel.
+
+use std::collections::BTreeMap;
+
+use futures::{future, Stream, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::{
+    error::SendRequest,
+    http::public::{order_book::BidAsk, OrderBook},
+    websocket::{
+        client::now_millis, frame::RECONNECTED, Channel, Frame, WsClient,
+        WsConnection, WsStream,
+    },
+};
+
+/// Message type of the full order book frames.
+pub(crate) const ORDER_BOOK_FULL: u16 = 431;
+/// Message type of the order book difference frames.
+pub(crate) const ORDER_BOOK_DIFF: u16 = 432;
+
+impl WsClient {
+    /// Subscribes to the order book of a pair. The stream first yields a
+    /// [`Snapshot`][OrderBookEvent::Snapshot] of the whole book and then an
+    /// [`Update`][OrderBookEvent::Update] for every change in the book.
+    ///
+    /// Use [`OrderBook::apply`] to fold the updates into the snapshot.
+    ///
+    /// If an update skips a sequence number, the stream yields a
+    /// [`SequenceGap`][SendRequest::SequenceGap] error. The local book is no
+    /// longer reliable in that case and the channel should be subscribed
+    /// again.
+    /// # Parameters
+    /// - `pair`: For example, `BTCUSDT`.
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or
+    /// subscribing to the channel. Errors occurring afterwards are yielded
+    /// by the stream.
+    ///
+    /// See also <https://docs.btcturk.com/websocket-feed/orderbook>.
+    pub async fn subscribe_order_book(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<OrderBookEvent>, SendRequest> {
+        let frames = self.subscribe("obdiff", &pair.into()).await?;
+        Ok(events(frames))
+    }
+}
+
+impl WsConnection {
+    /// Same as [`WsClient::subscribe_order_book`] but the order book is
+    /// received over this connection.
+    ///
+    /// A second subscription to the same pair doesn't receive a snapshot
+    /// since the feed sends it only when the channel is joined.
+    /// # Errors
+    /// [`SendRequest`] if there is an error subscribing to the channel.
+    /// Errors occurring afterwards are yielded by the stream.
+    pub async fn subscribe_order_book(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<OrderBookEvent>, SendRequest> {
+        let frames = self.subscribe(Channel::OrderBook, pair.into()).await?;
+        Ok(events(frames))
+    }
+}
+
+/// Item of the order book stream.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OrderBookEvent {
+    /// Whole order book. Its [`timestamp`][OrderBook::timestamp] is the local
+    /// time the snapshot was received at.
+    Snapshot(OrderBook),
+    /// Changes in the order book since the previous event.
+    Update(OrderBookUpdate),
+    /// Connection dropped and was opened again. Changes during the outage
+    /// are lost so a fresh [`Snapshot`][Self::Snapshot] follows this event.
+    /// See [`WsClient::reconnect`].
+    Reconnected,
+}
+
+/// Changes in the order book of a pair.
+///
+/// **Sample**:
+/// ```json
+#[doc = include_str!("update_sample.json")]
+/// ```
+/// A level with zero amount is removed from the book.
+///
+/// See also <https://docs.btcturk.com/websocket-feed/orderbook>
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderBookUpdate {
+    #[allow(missing_docs)]
+    pub pair_symbol: String,
+    /// Sequence number of the update. Each update is expected to follow the
+    /// previous snapshot or update by one.
+    pub change_set: u64,
+    #[allow(missing_docs)]
+    pub bids: ChangeSet,
+    #[allow(missing_docs)]
+    pub asks: ChangeSet,
+}
+
+/// Levels changed in one side of the order book.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChangeSet {
+    /// Levels to add to the book. A level replaces the existing one with the
+    /// same price.
+    pub added: Vec<BidAsk>,
+    /// Levels to remove from the book. Only the price is significant.
+    pub removed: Vec<BidAsk>,
+}
+
+impl From<Vec<BidAsk>> for ChangeSet {
+    fn from(levels: Vec<BidAsk>) -> Self {
+        let (removed, added) =
+            levels.into_iter().partition(|level| level.amount.is_zero());
+        Self { added, removed }
+    }
+}
+
+impl OrderBook {
+    /// Fold an update into the order book. Bids are kept in descending and
+    /// asks are kept in ascending order of price.
+    pub fn apply(&mut self, update: &OrderBookUpdate) {
+        fold(&mut self.bids, &update.bids, |a, b| b.cmp(a));
+        fold(&mut self.asks, &update.asks, Decimal::cmp);
+    }
+
+    /// Compute the changes from this book to the `other` one, e.g. between
+    /// two polls of [`Client::order_book`][crate::Client::order_book].
+    /// [Applying][Self::apply] the result to this book gives the levels of
+    /// the other one.
+    ///
+    /// The levels which are new or have a different amount in the other book
+    /// are added and the levels missing from it are removed with zero
+    /// amount. The [`pair_symbol`][OrderBookUpdate::pair_symbol] is empty and
+    /// the [`change_set`][OrderBookUpdate::change_set] is zero since the
+    /// books have none.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> OrderBookUpdate {
+        OrderBookUpdate {
+            pair_symbol: String::new(),
+            change_set: 0,
+            bids: changes(&self.bids, &other.bids),
+            asks: changes(&self.asks, &other.asks),
+        }
+    }
+}
+
+/// Compute the changes from the `old` levels to the `new` ones of one side.
+fn changes(old: &[BidAsk], new: &[BidAsk]) -> ChangeSet {
+    let amounts = |levels: &[BidAsk]| {
+        levels
+            .iter()
+            .map(|level| (level.price, level.amount))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let (old, new) = (amounts(old), amounts(new));
+    ChangeSet {
+        added: new
+            .iter()
+            .filter(|&(price, amount)| old.get(price) != Some(amount))
+            .map(|(&price, &amount)| BidAsk { price, amount })
+            .collect(),
+        removed: old
+            .keys()
+            .filter(|price| !new.contains_key(price))
+            .map(|&price| BidAsk {
+                price,
+                amount: Decimal::ZERO,
+            })
+            .collect(),
+    }
+}
+
+fn fold(
+    levels: &mut Vec<BidAsk>,
+    change_set: &ChangeSet,
+    order: impl Fn(&Decimal, &Decimal) -> std::cmp::Ordering,
+) {
+    for removed in &change_set.removed {
+        levels.retain(|level| level.price != removed.price);
+    }
+    for added in &change_set.added {
+        match levels.binary_search_by(|level| order(&level.price, &added.price))
+        {
+            Ok(index) => levels[index] = *added,
+            Err(index) => levels.insert(index, *added),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct OrderBookRaw {
+    #[serde(rename = "CS")]
+    change_set: u64,
+    #[serde(rename = "PS")]
+    pair_symbol: String,
+    #[serde(rename = "BO")]
+    bids: Vec<LevelRaw>,
+    #[serde(rename = "AO")]
+    asks: Vec<LevelRaw>,
+}
+
+#[derive(Deserialize)]
+struct LevelRaw {
+    #[serde(rename = "P")]
+    price: Decimal,
+    #[serde(rename = "A")]
+    amount: Decimal,
+}
+
+impl From<LevelRaw> for BidAsk {
+    fn from(raw: LevelRaw) -> Self {
+        Self {
+            price: raw.price,
+            amount: raw.amount,
+        }
+    }
+}
+
+fn levels(raw: Vec<LevelRaw>) -> Vec<BidAsk> {
+    raw.into_iter().map(BidAsk::from).collect()
+}
+
+impl OrderBookRaw {
+    /// The feed doesn't send a timestamp with the snapshot so the local time
+    /// is used.
+    pub(crate) fn into_snapshot(self) -> OrderBook {
+        OrderBook {
+            timestamp: now_millis(),
+            bids: levels(self.bids),
+            asks: levels(self.asks),
+        }
+    }
+
+    pub(crate) fn into_update(self) -> OrderBookUpdate {
+        OrderBookUpdate {
+            pair_symbol: self.pair_symbol,
+            change_set: self.change_set,
+            bids: levels(self.bids).into(),
+            asks: levels(self.asks).into(),
+        }
+    }
+}
+
+/// Turn the frames into order book events while keeping track of the
+/// sequence numbers.
+fn events(
+    frames: impl Stream<Item = Result<Frame, SendRequest>> + Send + 'static,
+) -> WsStream<OrderBookEvent> {
+    let mut last_change_set = None;
+    Box::pin(frames.filter_map(move |frame| {
+        future::ready(match frame {
+            Ok(frame)
+                if frame.r#type == ORDER_BOOK_FULL
+                    || frame.r#type == ORDER_BOOK_DIFF =>
+            {
+                Some(event(frame, &mut last_change_set))
+            }
+            Ok(frame) if frame.r#type == RECONNECTED => {
+                last_change_set = None;
+                Some(Ok(OrderBookEvent::Reconnected))
+            }
+            Ok(_) => None,
+            Err(error) => Some(Err(error)),
+        })
+    }))
+}
+
+fn event(
+    frame: Frame,
+    last_change_set: &mut Option<u64>,
+) -> Result<OrderBookEvent, SendRequest> {
+    let full = frame.r#type == ORDER_BOOK_FULL;
+    let raw = frame.payload::<OrderBookRaw>()?;
+    let expected = last_change_set.replace(raw.change_set).map(|last| last + 1);
+    if full {
+        return Ok(OrderBookEvent::Snapshot(raw.into_snapshot()));
+    }
+    match expected {
+        Some(expected) if expected != raw.change_set => {
+            Err(SendRequest::SequenceGap {
+                expected,
+                received: raw.change_set,
+            })
+        }
+        _ => Ok(OrderBookEvent::Update(raw.into_update())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::assert_eq;
+    use rust_decimal_macros::dec;
+
+    use super::{events, OrderBookEvent, OrderBookUpdate};
+    use crate::{
+        error::SendRequest,
+        http::public::{order_book::BidAsk, OrderBook},
+        websocket::{Frame, WsClient},
+    };
+
+    fn frame(json_string: &str) -> Frame {
+        serde_json::from_str::<Frame>(json_string).unwrap()
+    }
+
+    fn snapshot_and_update() -> (OrderBook, OrderBookUpdate) {
+        let frames = vec![
+            Ok(frame(include_str!("snapshot_sample.json"))),
+            Ok(frame(include_str!("update_sample.json"))),
+        ];
+        let mut events =
+            block_on(events(stream::iter(frames)).collect::<Vec<_>>())
+                .into_iter()
+                .map(Result::unwrap);
+        let Some(OrderBookEvent::Snapshot(order_book)) = events.next() else {
+            panic!("expected a snapshot");
+        };
+        let Some(OrderBookEvent::Update(update)) = events.next() else {
+            panic!("expected an update");
+        };
+        (order_book, update)
+    }
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_order_book() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let event = WsClient::new()
+            .subscribe_order_book("BTCUSDT")
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(event, OrderBookEvent::Snapshot(_)));
+    }
+
+    #[test]
+    fn deserialize_order_book_events() {
+        let (order_book, update) = snapshot_and_update();
+        assert_eq!(order_book.bids.len(), 2);
+        assert_eq!(order_book.asks.len(), 2);
+        assert_eq!(update.change_set, 1025);
+        assert_eq!(update.asks.removed.len(), 1);
+        assert_eq!(update.asks.added.len(), 1);
+        assert_eq!(update.bids.added.len(), 2);
+    }
+
+    #[test]
+    fn apply_update() {
+        let (mut order_book, update) = snapshot_and_update();
+        order_book.apply(&update);
+        let level = |price, amount| BidAsk { price, amount };
+        assert_eq!(
+            order_book.bids,
+            vec![
+                level(dec!(36380), dec!(0.02)),
+                level(dec!(36371), dec!(0.1)),
+                level(dec!(36370), dec!(0.24053385)),
+            ]
+        );
+        assert_eq!(
+            order_book.asks,
+            vec![
+                level(dec!(36402), dec!(0.00276815)),
+                level(dec!(36405), dec!(1.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_snapshots() {
+        let (old, update) = snapshot_and_update();
+        let mut new = old.clone();
+        new.apply(&update);
+        let diff = old.diff(&new);
+        assert_eq!(diff.bids.added.len(), 2);
+        assert_eq!(diff.asks.added.len(), 1);
+        assert_eq!(diff.asks.removed.len(), 1);
+        let mut patched = old;
+        patched.apply(&diff);
+        assert_eq!(patched.bids, new.bids);
+        assert_eq!(patched.asks, new.asks);
+
+        let diff = new.diff(&new);
+        assert!(diff.bids.added.is_empty() && diff.bids.removed.is_empty());
+        assert!(diff.asks.added.is_empty() && diff.asks.removed.is_empty());
+    }
+
+    #[test]
+    fn restart_sequence_after_reconnect() {
+        let mut update = frame(include_str!("update_sample.json"));
+        update.payload["CS"] = 1030.into();
+        let frames = vec![
+            Ok(frame(include_str!("snapshot_sample.json"))),
+            Ok(Frame::reconnected()),
+            Ok(update),
+        ];
+        let results =
+            block_on(events(stream::iter(frames)).collect::<Vec<_>>());
+        assert!(matches!(results[1], Ok(OrderBookEvent::Reconnected)));
+        assert!(matches!(results[2], Ok(OrderBookEvent::Update(_))));
+    }
+
+    #[test]
+    fn detect_sequence_gap() {
+        let mut update = frame(include_str!("update_sample.json"));
+        update.payload["CS"] = 1030.into();
+        let frames =
+            vec![Ok(frame(include_str!("snapshot_sample.json"))), Ok(update)];
+        let results =
+            block_on(events(stream::iter(frames)).collect::<Vec<_>>());
+        match &results[1] {
+            Err(SendRequest::SequenceGap { expected, received }) => {
+                assert_eq!(*expected, 1025);
+                assert_eq!(*received, 1030);
+            }
+            other => panic!("unexpected result: `{other:?}`"),
+        }
+    }
+}
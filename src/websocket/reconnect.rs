@@ -0,0 +1,171 @@
+//! Automatic reconnection of the websocket subscriptions.
+
+use std::{sync::Arc, time::Duration};
+
+use futures::{future::BoxFuture, stream, StreamExt};
+use futures_timer::Delay;
+
+use crate::error::SendRequest;
+
+use super::{Frame, WsStream};
+
+/// Opens a new connection and sends the subscription frames again.
+pub type Connect = Arc<
+    dyn Fn() -> BoxFuture<'static, Result<WsStream<Frame>, SendRequest>>
+        + Send
+        + Sync,
+>;
+
+/// Decides whether and when a dropped connection is opened again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReconnectPolicy {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl ReconnectPolicy {
+    pub const fn new(max_retries: u32, backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            backoff,
+        }
+    }
+
+    /// Wait twice as long as the previous attempt.
+    pub const fn delay(&self, retries: u32) -> Duration {
+        self.backoff.saturating_mul(2_u32.saturating_pow(retries))
+    }
+}
+
+struct State {
+    frames: Option<WsStream<Frame>>,
+    connect: Connect,
+    policy: ReconnectPolicy,
+    retries: u32,
+    error: Option<SendRequest>,
+}
+
+/// Pass the frames through and open a new connection whenever the current
+/// one drops. A [`reconnected`][Frame::reconnected] frame is yielded after
+/// each new connection. If all the attempts fail, the last error is yielded
+/// and the stream ends.
+pub fn reconnecting(
+    frames: WsStream<Frame>,
+    connect: Connect,
+    policy: ReconnectPolicy,
+) -> WsStream<Frame> {
+    let state = State {
+        frames: Some(frames),
+        connect,
+        policy,
+        retries: 0,
+        error: None,
+    };
+    Box::pin(stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        loop {
+            if let Some(frames) = state.frames.as_mut() {
+                match frames.next().await {
+                    Some(Err(
+                        error @ (SendRequest::ConnectionClosed
+                        | SendRequest::WebsocketError { .. }),
+                    )) => {
+                        log::warn!("websocket connection dropped: {error}");
+                        state.frames = None;
+                        state.error = Some(error);
+                    }
+                    None => {
+                        state.frames = None;
+                        state.error = Some(SendRequest::ConnectionClosed);
+                    }
+                    Some(item) => return Some((item, Some(state))),
+                }
+            } else if state.retries >= state.policy.max_retries {
+                let error =
+                    state.error.take().unwrap_or(SendRequest::ConnectionClosed);
+                return Some((Err(error), None));
+            } else {
+                Delay::new(state.policy.delay(state.retries)).await;
+                state.retries += 1;
+                match (state.connect)().await {
+                    Ok(frames) => {
+                        state.frames = Some(frames);
+                        state.retries = 0;
+                        state.error = None;
+                        return Some((Ok(Frame::reconnected()), Some(state)));
+                    }
+                    Err(error) => {
+                        log::warn!("failed to reconnect: {error}");
+                        state.error = Some(error);
+                    }
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::{executor::block_on, stream, FutureExt, StreamExt};
+    use pretty_assertions::assert_eq;
+
+    use super::{reconnecting, Connect, ReconnectPolicy};
+    use crate::{
+        error::SendRequest,
+        websocket::{frame::RECONNECTED, Frame, WsStream},
+    };
+
+    fn frames(r#type: u16) -> WsStream<Frame> {
+        Box::pin(stream::iter(vec![
+            Ok(Frame {
+                r#type,
+                payload: serde_json::Value::Null,
+            }),
+            Err(SendRequest::ConnectionClosed),
+        ]))
+    }
+
+    #[test]
+    fn reconnect_until_retries_run_out() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let connect: Connect = {
+            let attempts = Arc::clone(&attempts);
+            Arc::new(move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if attempt == 0 {
+                        Ok(frames(2))
+                    } else {
+                        Err(SendRequest::ConnectionClosed)
+                    }
+                }
+                .boxed()
+            })
+        };
+        let policy = ReconnectPolicy::new(2, Duration::ZERO);
+        let results = block_on(
+            reconnecting(frames(1), connect, policy).collect::<Vec<_>>(),
+        );
+        let types = results
+            .iter()
+            .map(|result| result.as_ref().map(|frame| frame.r#type).ok())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec![Some(1), Some(RECONNECTED), Some(2), None]);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn back_off_exponentially() {
+        let policy = ReconnectPolicy::new(3, Duration::from_millis(10));
+        assert_eq!(policy.delay(0), Duration::from_millis(10));
+        assert_eq!(policy.delay(2), Duration::from_millis(40));
+    }
+}
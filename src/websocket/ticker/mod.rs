@@ -0,0 +1,240 @@
+//! Implementation of the ticker channel.
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::{
+    error::SendRequest,
+    http::public::Ticker,
+    websocket::{
+        client::{channel, now_millis},
+        Channel, WsClient, WsConnection, WsStream,
+    },
+};
+
+/// Message type of the ticker frames of all the pairs.
+pub(crate) const TICKER_ALL: u16 = 401;
+/// Message type of the ticker frames of a single pair.
+pub(crate) const TICKER_PAIR: u16 = 402;
+
+/// Event of the ticker channel which streams the tickers of all the pairs.
+const ALL_PAIRS: &str = "all";
+
+impl WsClient {
+    /// Subscribes to the ticker channel of a pair and streams the
+    /// snapshot information about the last trade (tick), best bid/ask and
+    /// 24h volume as they change.
+    ///
+    /// The feed doesn't send a timestamp with the ticker so the
+    /// [`timestamp`][Ticker::timestamp] field is set to the local time
+    /// the ticker was received at.
+    /// # Parameters
+    /// - `pair`: For example, `BTCUSDT`.
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or
+    /// subscribing to the channel. Errors occurring afterwards are yielded
+    /// by the stream.
+    ///
+    /// See also <https://docs.btcturk.com/websocket-feed/ticker>.
+    pub async fn subscribe_ticker(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<Ticker>, SendRequest> {
+        let frames = self.subscribe("ticker", &pair.into()).await?;
+        Ok(channel::<TickerRaw, Ticker>(frames, TICKER_PAIR))
+    }
+
+    /// Subscribes to the ticker channel of all the pairs and streams the
+    /// tickers of the whole market in batches. This is the live equivalent
+    /// of [`tickers`][crate::Client::tickers].
+    ///
+    /// The timestamps are set to the local time as in
+    /// [`subscribe_ticker`][Self::subscribe_ticker].
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or
+    /// subscribing to the channel. Errors occurring afterwards are yielded
+    /// by the stream.
+    ///
+    /// See also <https://docs.btcturk.com/websocket-feed/ticker>.
+    pub async fn subscribe_all_tickers(
+        &self,
+    ) -> Result<WsStream<Vec<Ticker>>, SendRequest> {
+        let frames = self.subscribe("ticker", ALL_PAIRS).await?;
+        Ok(channel::<TickerAllRaw, Vec<Ticker>>(frames, TICKER_ALL))
+    }
+}
+
+impl WsConnection {
+    /// Same as [`WsClient::subscribe_ticker`] but the ticker is received
+    /// over this connection.
+    /// # Errors
+    /// [`SendRequest`] if there is an error subscribing to the channel.
+    /// Errors occurring afterwards are yielded by the stream.
+    pub async fn subscribe_ticker(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<Ticker>, SendRequest> {
+        let frames = self.subscribe(Channel::Ticker, pair.into()).await?;
+        Ok(channel::<TickerRaw, Ticker>(frames, TICKER_PAIR))
+    }
+
+    /// Same as [`WsClient::subscribe_all_tickers`] but the tickers are
+    /// received over this connection.
+    /// # Errors
+    /// [`SendRequest`] if there is an error subscribing to the channel.
+    /// Errors occurring afterwards are yielded by the stream.
+    pub async fn subscribe_all_tickers(
+        &self,
+    ) -> Result<WsStream<Vec<Ticker>>, SendRequest> {
+        let frames = self
+            .subscribe(Channel::Ticker, ALL_PAIRS.to_owned())
+            .await?;
+        Ok(channel::<TickerAllRaw, Vec<Ticker>>(frames, TICKER_ALL))
+    }
+}
+
+/// **Sample**:
+/// ```json
+#[doc = include_str!("sample.json")]
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TickerRaw {
+    #[serde(rename = "PS")]
+    pair: String,
+    #[serde(rename = "NS")]
+    numerator_symbol: String,
+    #[serde(rename = "DS")]
+    denominator_symbol: String,
+    #[serde(rename = "LA")]
+    last: Decimal,
+    #[serde(rename = "H")]
+    high: Decimal,
+    #[serde(rename = "L")]
+    low: Decimal,
+    #[serde(rename = "B")]
+    bid: Decimal,
+    #[serde(rename = "A")]
+    ask: Decimal,
+    #[serde(rename = "O")]
+    open: Decimal,
+    #[serde(rename = "V")]
+    volume: Decimal,
+    #[serde(rename = "AV")]
+    average: Decimal,
+    #[serde(rename = "D")]
+    daily: Decimal,
+    #[serde(rename = "DP")]
+    daily_percent: Decimal,
+    #[serde(rename = "Ord")]
+    order: u64,
+}
+
+/// **Sample**:
+/// ```json
+#[doc = include_str!("all_sample.json")]
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TickerAllRaw {
+    items: Vec<TickerRaw>,
+}
+
+impl From<TickerAllRaw> for Vec<Ticker> {
+    fn from(raw: TickerAllRaw) -> Self {
+        raw.items.into_iter().map(Ticker::from).collect()
+    }
+}
+
+impl From<TickerRaw> for Ticker {
+    fn from(raw: TickerRaw) -> Self {
+        Self {
+            pair_normalized: format!(
+                "{}_{}",
+                raw.numerator_symbol, raw.denominator_symbol
+            ),
+            pair: raw.pair,
+            timestamp: now_millis(),
+            last: raw.last,
+            high: raw.high,
+            low: raw.low,
+            bid: raw.bid,
+            ask: raw.ask,
+            open: raw.open,
+            volume: raw.volume,
+            average: raw.average,
+            daily: raw.daily,
+            daily_percent: raw.daily_percent,
+            denominator_symbol: raw.denominator_symbol,
+            numerator_symbol: raw.numerator_symbol,
+            order: raw.order,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use rust_decimal::Decimal;
+
+    use super::{TickerAllRaw, TickerRaw, TICKER_ALL, TICKER_PAIR};
+    use crate::{
+        http::public::Ticker,
+        websocket::{Frame, WsClient},
+    };
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_ticker() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let ticker = WsClient::new()
+            .subscribe_ticker("BTCUSDT")
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_str_eq!(ticker.pair_normalized, "BTC_USDT");
+        assert!(ticker.last > Decimal::ZERO);
+    }
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_all_tickers() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let tickers = WsClient::new()
+            .subscribe_all_tickers()
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(tickers.iter().any(|ticker| ticker.pair == "BTCUSDT"));
+    }
+
+    #[test]
+    fn deserialize_ticker() {
+        let json_string = include_str!("sample.json");
+        let frame = serde_json::from_str::<Frame>(json_string).unwrap();
+        assert_eq!(frame.r#type, TICKER_PAIR);
+        let ticker = Ticker::from(frame.payload::<TickerRaw>().unwrap());
+        assert_str_eq!(ticker.pair, "BTCUSDT");
+        assert_str_eq!(ticker.pair_normalized, "BTC_USDT");
+        assert_eq!(ticker.order, 2001);
+    }
+
+    #[test]
+    fn deserialize_all_tickers() {
+        let json_string = include_str!("all_sample.json");
+        let frame = serde_json::from_str::<Frame>(json_string).unwrap();
+        assert_eq!(frame.r#type, TICKER_ALL);
+        let tickers =
+            Vec::<Ticker>::from(frame.payload::<TickerAllRaw>().unwrap());
+        assert_eq!(tickers.len(), 2);
+        assert_str_eq!(tickers[1].pair, "ETHUSDT");
+        assert_str_eq!(tickers[1].pair_normalized, "ETH_USDT");
+    }
+}
@@ -0,0 +1,190 @@
+//! Implementation of the trade channel.
+
+use rust_decimal::Decimal;
+use serde::{de::Error, Deserialize, Deserializer};
+
+use crate::{
+    error::SendRequest,
+    http::{public::Trade, OrderType, PairSymbol},
+    websocket::{client::channel, Channel, WsClient, WsConnection, WsStream},
+};
+
+/// Message type of the frames of a single trade.
+pub(crate) const TRADE_SINGLE: u16 = 422;
+
+impl WsClient {
+    /// Subscribes to the trade channel of a pair and streams the public
+    /// trades as they happen.
+    ///
+    /// The feed doesn't send the numerator and the denominator of the pair
+    /// with the trades. [`numerator`][Trade::numerator],
+    /// [`denominator`][Trade::denominator] and
+    /// [`pair_normalized`][Trade::pair_normalized] are derived from the pair
+    /// symbol for the [`DENOMINATORS`][PairSymbol::DENOMINATORS]. Otherwise,
+    /// the first two are left empty and the last one is the same as the pair.
+    /// # Parameters
+    /// - `pair`: For example, `BTCUSDT`.
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or
+    /// subscribing to the channel. Errors occurring afterwards are yielded
+    /// by the stream.
+    pub async fn subscribe_trades(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<Trade>, SendRequest> {
+        let frames = self.subscribe("trade", &pair.into()).await?;
+        Ok(channel::<TradeRaw, Trade>(frames, TRADE_SINGLE))
+    }
+}
+
+impl WsConnection {
+    /// Same as [`WsClient::subscribe_trades`] but the trades are received
+    /// over this connection.
+    /// # Errors
+    /// [`SendRequest`] if there is an error subscribing to the channel.
+    /// Errors occurring afterwards are yielded by the stream.
+    pub async fn subscribe_trades(
+        &self,
+        pair: impl Into<String> + Send,
+    ) -> Result<WsStream<Trade>, SendRequest> {
+        let frames = self.subscribe(Channel::Trade, pair.into()).await?;
+        Ok(channel::<TradeRaw, Trade>(frames, TRADE_SINGLE))
+    }
+}
+
+/// **Sample**:
+/// ```json
+#[doc = include_str!("sample.json")]
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TradeRaw {
+    #[serde(rename = "PS")]
+    pair: String,
+    #[serde(rename = "D", deserialize_with = "number")]
+    date: u64,
+    #[serde(rename = "I")]
+    trade_id: String,
+    #[serde(rename = "P")]
+    price: Decimal,
+    #[serde(rename = "A")]
+    amount: Decimal,
+    #[serde(rename = "S", deserialize_with = "side")]
+    side: OrderType,
+}
+
+impl From<TradeRaw> for Trade {
+    fn from(raw: TradeRaw) -> Self {
+        // The feed doesn't send the denominator of the pair with the
+        // trades so it is derived from the pair symbol.
+        let (numerator, denominator, pair_normalized) =
+            match raw.pair.parse::<PairSymbol>() {
+                Ok(pair) => (
+                    pair.numerator().to_owned(),
+                    pair.denominator().to_owned(),
+                    format!("{}_{}", pair.numerator(), pair.denominator()),
+                ),
+                Err(_) => (String::new(), String::new(), raw.pair.clone()),
+            };
+        Self {
+            pair: raw.pair,
+            pair_normalized,
+            numerator,
+            denominator,
+            date: raw.date,
+            trade_id: raw.trade_id,
+            price: raw.price,
+            amount: raw.amount,
+            side: raw.side,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(u64),
+}
+
+/// The feed may send numbers as strings.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(string) => string.parse().map_err(Error::custom),
+        StringOrNumber::Number(number) => Ok(number),
+    }
+}
+
+/// The feed sends the side as `0` for buy and `1` for sell. Parse it the
+/// same way as the text sent by the REST endpoints.
+fn side<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<OrderType, D::Error> {
+    let side = match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(string) => string,
+        StringOrNumber::Number(0) => "buy".to_owned(),
+        StringOrNumber::Number(1) => "sell".to_owned(),
+        StringOrNumber::Number(number) => number.to_string(),
+    };
+    OrderType::try_from(side).map_err(Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use rust_decimal::Decimal;
+
+    use super::{TradeRaw, TRADE_SINGLE};
+    use crate::{
+        http::{public::Trade, OrderType},
+        websocket::{Frame, WsClient},
+    };
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_trades() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let trade = WsClient::new()
+            .subscribe_trades("BTCUSDT")
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_str_eq!(trade.pair, "BTCUSDT");
+        assert!(trade.price > Decimal::ZERO);
+    }
+
+    #[test]
+    fn deserialize_trade() {
+        let json_string = include_str!("sample.json");
+        let frame = serde_json::from_str::<Frame>(json_string).unwrap();
+        assert_eq!(frame.r#type, TRADE_SINGLE);
+        let mut raw = frame.payload::<TradeRaw>().unwrap();
+        let trade = Trade::from(raw.clone());
+        assert_str_eq!(trade.pair_normalized, "BTC_USDT");
+        assert_str_eq!(trade.numerator, "BTC");
+        assert_str_eq!(trade.denominator, "USDT");
+        assert_eq!(trade.date, 1_643_883_524_310);
+        assert_eq!(trade.side, OrderType::Sell);
+        raw.pair = "BTCXYZ".to_owned();
+        let trade = Trade::from(raw);
+        assert_str_eq!(trade.pair_normalized, "BTCXYZ");
+        assert!(trade.numerator.is_empty() && trade.denominator.is_empty());
+    }
+
+    #[test]
+    fn reject_unknown_side() {
+        let mut frame =
+            serde_json::from_str::<Frame>(include_str!("sample.json")).unwrap();
+        frame.payload["S"] = "buy".into();
+        assert_eq!(
+            frame.clone().payload::<TradeRaw>().unwrap().side,
+            OrderType::Buy
+        );
+        frame.payload["S"] = 2.into();
+        assert!(frame.payload::<TradeRaw>().is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Implementation of the authenticated user orders channel.
+
+use futures::{future, Stream, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::{
+    error::SendRequest,
+    http::{OrderMethod, OrderStatus, OrderType},
+    websocket::{frame::RECONNECTED, Frame, WsClient, WsStream},
+    ApiKeys,
+};
+
+/// Message type of the frames of matched orders.
+pub(crate) const ORDER_MATCHED: u16 = 441;
+/// Message type of the frames of new orders.
+pub(crate) const ORDER_INSERT: u16 = 451;
+/// Message type of the frames of canceled orders.
+pub(crate) const ORDER_DELETE: u16 = 452;
+
+impl WsClient {
+    /// Logs in to the feed with the given keys and streams the changes in
+    /// the orders of the user as they happen. This is a faster alternative
+    /// to polling [`open_orders`][crate::Client::open_orders] to learn about
+    /// the fills.
+    ///
+    /// The login is signed the same way as the requests to the private
+    /// endpoints, adjusted by the [`clock_offset`][WsClient::clock_offset].
+    /// # Errors
+    /// [`SendRequest`] if there is an error connecting to the feed or the
+    /// feed rejects the login with a
+    /// [`LoginFailed`][SendRequest::LoginFailed] error. Errors occurring
+    /// afterwards are yielded by the stream.
+    pub async fn subscribe_user_orders(
+        &self,
+        keys: &ApiKeys,
+    ) -> Result<WsStream<UserOrderEvent>, SendRequest> {
+        let frames = self.login(keys).await?;
+        Ok(events(frames))
+    }
+}
+
+/// Item of the user orders stream.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UserOrderEvent {
+    /// Order is submitted and placed on the book.
+    New(UserOrder),
+    /// Some or all of the order's amount is matched.
+    Matched(OrderMatch),
+    /// Order is canceled and removed from the book.
+    Canceled(UserOrder),
+    /// Connection dropped and was opened again. Changes during the outage
+    /// are lost so query the [`open_orders`][crate::Client::open_orders] to
+    /// catch up. See [`WsClient::reconnect`].
+    Reconnected,
+}
+
+/// Order of the user which is placed on or removed from the book.
+///
+/// **Sample**:
+/// ```json
+#[doc = include_str!("new_sample.json")]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserOrder {
+    #[allow(missing_docs)]
+    pub id: i64,
+    #[allow(missing_docs)]
+    pub pair_symbol: String,
+    #[allow(missing_docs)]
+    pub r#type: OrderType,
+    #[allow(missing_docs)]
+    pub method: OrderMethod,
+    #[allow(missing_docs)]
+    pub price: Decimal,
+    #[allow(missing_docs)]
+    pub stop_price: Option<Decimal>,
+    /// Amount of the order which is not matched yet.
+    pub amount: Decimal,
+    /// [`Untouched`][OrderStatus::Untouched] for new orders and
+    /// [`Canceled`][OrderStatus::Canceled] for canceled orders.
+    pub status: OrderStatus,
+    /// `None` if the order was submitted without a client identifier.
+    pub new_order_client_id: Option<String>,
+    /// Time of the change in milliseconds.
+    pub timestamp: u64,
+}
+
+/// Execution of an order of the user.
+///
+/// **Sample**:
+/// ```json
+#[doc = include_str!("matched_sample.json")]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderMatch {
+    /// Identifier of the matched order.
+    pub id: i64,
+    #[allow(missing_docs)]
+    pub pair_symbol: String,
+    #[allow(missing_docs)]
+    pub r#type: OrderType,
+    #[allow(missing_docs)]
+    pub method: OrderMethod,
+    /// Price of the match.
+    pub price: Decimal,
+    /// Amount matched in this execution.
+    pub amount: Decimal,
+    /// Amount of the order which is not matched yet.
+    pub amount_left: Decimal,
+    /// [`Filled`][OrderStatus::Filled] if nothing is left to match,
+    /// [`PartiallyFilled`][OrderStatus::PartiallyFilled] otherwise.
+    pub status: OrderStatus,
+    /// `None` if the order was submitted without a client identifier.
+    pub new_order_client_id: Option<String>,
+    /// Time of the match in milliseconds.
+    pub timestamp: u64,
+}
+
+#[cfg(feature = "chrono")]
+impl UserOrder {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl OrderMatch {
+    /// Get [`timestamp`][Self::timestamp] as a date and time in UTC.
+    #[must_use]
+    pub fn datetime(&self) -> chrono::DateTime<chrono::Utc> {
+        crate::datetime::from_millis(self.timestamp)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UserOrderRaw {
+    id: i64,
+    pair_symbol: String,
+    side: OrderType,
+    method: OrderMethod,
+    price: Decimal,
+    stop_price: Option<Decimal>,
+    amount: Decimal,
+    #[serde(default, deserialize_with = "crate::http::empty_as_none")]
+    new_order_client_id: Option<String>,
+    timestamp: u64,
+}
+
+impl UserOrderRaw {
+    fn into_order(self, status: OrderStatus) -> UserOrder {
+        UserOrder {
+            id: self.id,
+            pair_symbol: self.pair_symbol,
+            r#type: self.side,
+            method: self.method,
+            price: self.price,
+            stop_price: self.stop_price.filter(|price| !price.is_zero()),
+            amount: self.amount,
+            status,
+            new_order_client_id: self.new_order_client_id,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderMatchRaw {
+    id: i64,
+    pair_symbol: String,
+    side: OrderType,
+    method: OrderMethod,
+    price: Decimal,
+    amount: Decimal,
+    amount_left: Decimal,
+    #[serde(default, deserialize_with = "crate::http::empty_as_none")]
+    new_order_client_id: Option<String>,
+    timestamp: u64,
+}
+
+impl From<OrderMatchRaw> for OrderMatch {
+    fn from(raw: OrderMatchRaw) -> Self {
+        Self {
+            id: raw.id,
+            pair_symbol: raw.pair_symbol,
+            r#type: raw.side,
+            method: raw.method,
+            price: raw.price,
+            amount: raw.amount,
+            amount_left: raw.amount_left,
+            status: if raw.amount_left.is_zero() {
+                OrderStatus::Filled
+            } else {
+                OrderStatus::PartiallyFilled
+            },
+            new_order_client_id: raw.new_order_client_id,
+            timestamp: raw.timestamp,
+        }
+    }
+}
+
+/// Turn the frames into user order events. Frames of other types are
+/// skipped.
+fn events(
+    frames: impl Stream<Item = Result<Frame, SendRequest>> + Send + 'static,
+) -> WsStream<UserOrderEvent> {
+    Box::pin(frames.filter_map(|frame| {
+        future::ready(match frame {
+            Ok(frame) => event(frame).map(Result::ok).transpose(),
+            Err(error) => Some(Err(error)),
+        })
+    }))
+}
+
+/// Turn the frame into a user order event. The frames of other types are
+/// given back as they are.
+pub(crate) fn event(
+    frame: Frame,
+) -> Result<Result<UserOrderEvent, Frame>, SendRequest> {
+    Ok(Ok(match frame.r#type {
+        ORDER_INSERT => UserOrderEvent::New(
+            frame
+                .payload::<UserOrderRaw>()?
+                .into_order(OrderStatus::Untouched),
+        ),
+        ORDER_MATCHED => {
+            UserOrderEvent::Matched(frame.payload::<OrderMatchRaw>()?.into())
+        }
+        ORDER_DELETE => UserOrderEvent::Canceled(
+            frame
+                .payload::<UserOrderRaw>()?
+                .into_order(OrderStatus::Canceled),
+        ),
+        RECONNECTED => UserOrderEvent::Reconnected,
+        _ => return Ok(Err(frame)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::{assert_eq, assert_str_eq};
+    use rust_decimal_macros::dec;
+
+    use super::{events, UserOrderEvent};
+    use crate::{
+        http::{OrderMethod, OrderStatus, OrderType},
+        websocket::{Frame, WsClient},
+        ApiKeys,
+    };
+
+    fn frame(json_string: &str) -> Frame {
+        serde_json::from_str::<Frame>(json_string).unwrap()
+    }
+
+    #[ignore]
+    #[async_std::test]
+    async fn subscribe_user_orders() {
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let _orders = WsClient::new()
+            .subscribe_user_orders(&ApiKeys::load_from_env_var())
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn deserialize_user_order_events() {
+        let frames = vec![
+            Ok(frame(include_str!("new_sample.json"))),
+            Ok(frame(r#"[991, {"type": 991}]"#)),
+            Ok(frame(include_str!("matched_sample.json"))),
+            Ok(frame(include_str!("canceled_sample.json"))),
+        ];
+        let events = block_on(events(stream::iter(frames)).collect::<Vec<_>>())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 3);
+
+        let UserOrderEvent::New(order) = &events[0] else {
+            panic!("expected a new order");
+        };
+        assert_str_eq!(order.pair_symbol, "BTCTRY");
+        assert_eq!(order.r#type, OrderType::Buy);
+        assert_eq!(order.method, OrderMethod::Limit);
+        assert_eq!(order.stop_price, None);
+        assert_eq!(order.status, OrderStatus::Untouched);
+        assert_eq!(order.new_order_client_id.as_deref(), Some("test"));
+
+        let UserOrderEvent::Matched(order_match) = &events[1] else {
+            panic!("expected a match");
+        };
+        assert_eq!(order_match.id, order.id);
+        assert_eq!(order_match.amount, dec!(0.0004));
+        assert_eq!(order_match.status, OrderStatus::PartiallyFilled);
+
+        let UserOrderEvent::Canceled(canceled) = &events[2] else {
+            panic!("expected a canceled order");
+        };
+        assert_eq!(canceled.amount, dec!(0.0006));
+        assert_eq!(canceled.status, OrderStatus::Canceled);
+    }
+
+    #[test]
+    fn empty_client_id_is_none() {
+        let frames = [
+            include_str!("new_sample.json"),
+            include_str!("matched_sample.json"),
+        ]
+        .map(|sample| Ok(frame(&sample.replace(r#""test""#, r#""""#))));
+        let events = block_on(events(stream::iter(frames)).collect::<Vec<_>>());
+        let UserOrderEvent::New(order) = events[0].as_ref().unwrap() else {
+            panic!("expected a new order");
+        };
+        assert_eq!(order.new_order_client_id, None);
+        let UserOrderEvent::Matched(order_match) = events[1].as_ref().unwrap()
+        else {
+            panic!("expected a match");
+        };
+        assert_eq!(order_match.new_order_client_id, None);
+    }
+}
@@ -9,7 +9,12 @@ use pretty_assertions::assert_eq;
 async fn general_test() {
     let _ = env_logger::builder().is_test(true).try_init();
 
-    let mut client = Client::new(None, None).unwrap();
+    // The server may ban this IP for sending too many requests in a short
+    // while. See https://docs.btcturk.com/rate-limits.
+    let mut client = Client::builder()
+        .rate_limit(10, Duration::from_secs(1))
+        .build()
+        .unwrap();
 
     let ticker = client.ticker("BTCUSDT").await.unwrap();
     info!("Received ticker: {:?}", ticker);
@@ -37,11 +42,7 @@ async fn general_test() {
 
     let open_orders = client.open_orders("BTCUSDT").await.unwrap();
 
-    for order in open_orders.asks.iter().chain(open_orders.bids.iter()) {
-        // Wait a little bit otherwise the server may ban this IP for sending
-        // too many requests in a short while.
-        // See https://docs.btcturk.com/rate-limits.
-        async_std::task::sleep(Duration::from_millis(100)).await;
+    for order in open_orders.all() {
         client.cancel_order(order.id).await.unwrap();
     }
 }